@@ -0,0 +1,116 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use interest_calculator::{
+    BusinessDayConvention, CompoundingStrategy, Engine, InteractiveCalculator,
+    LoanExtraInstallment, LoanInitialization, LoanInterestChange, LoanRecurringExtraInstallments,
+    MonthlyDueDate, RecurringInterval,
+};
+
+use chrono::{Duration, Month, NaiveDate};
+use std::num::NonZeroU32;
+
+fn mortgage(terms: u32, engine: Engine) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 3000000.0,
+        nominal_interest: 3.5,
+        administration_fee: 1500.0,
+        installment_fee: 50.0,
+        terms,
+        due_within_month: MonthlyDueDate::Date(15),
+        first_installment_month: Month::February,
+        business_day_convention: BusinessDayConvention::Following,
+        ..LoanInitialization::default()
+    };
+
+    let mut calculator = InteractiveCalculator::new(NaiveDate::from_ymd(2021, 1, 5), initial);
+    calculator.set_engine(engine);
+    calculator
+        .add_event_interest_change(
+            NaiveDate::from_ymd(2024, 3, 1),
+            LoanInterestChange {
+                nominal_interest: 4.5,
+            },
+        )
+        .unwrap();
+    calculator
+}
+
+/// A 30-year loan with a monthly extra payment for 20 years and a single extra payment
+/// every quarter.
+fn many_extras() -> InteractiveCalculator {
+    let mut calculator = mortgage(360, Engine::EventDriven);
+    calculator
+        .add_event_extra_recurring(
+            NaiveDate::from_ymd(2021, 3, 1),
+            LoanRecurringExtraInstallments {
+                amount: 1000.0,
+                count: NonZeroU32::new(240).unwrap(),
+                recurring_interval: RecurringInterval::Monthly,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    for quarter in 0..80 {
+        calculator
+            .add_event_extra_single(
+                NaiveDate::from_ymd(2021, 2, 20) + Duration::days(91 * quarter),
+                LoanExtraInstallment {
+                    amount: 5000.0,
+                    policy: None,
+                    prepayment_fee: None,
+                },
+            )
+            .unwrap();
+    }
+    calculator
+}
+
+/// Compare stepping through every day of the loan with jumping between significant dates.
+fn engines(c: &mut Criterion) {
+    let mut group = c.benchmark_group("engine");
+    for terms in [12, 360].iter() {
+        for engine in [Engine::Daily, Engine::EventDriven].iter() {
+            let calculator = mortgage(*terms, *engine);
+            group.bench_with_input(
+                BenchmarkId::new(format!("{:?}", engine), terms),
+                &calculator,
+                // A clone does not keep the results of previous calculations.
+                |b, calculator| b.iter(|| calculator.clone().compute().unwrap()),
+            );
+        }
+    }
+    group.finish();
+}
+
+fn extra_payments(c: &mut Criterion) {
+    let calculator = many_extras();
+    c.benchmark_group("extras").bench_with_input(
+        BenchmarkId::new("terms", 360),
+        &calculator,
+        |b, calculator| b.iter(|| calculator.clone().compute().unwrap()),
+    );
+}
+
+fn compounding_strategies(c: &mut Criterion) {
+    let mut group = c.benchmark_group("compounding");
+    for strategy in [
+        CompoundingStrategy::OnInstallment,
+        CompoundingStrategy::Daily,
+        CompoundingStrategy::EndOfMonth,
+        CompoundingStrategy::EndOfYear,
+    ]
+    .iter()
+    {
+        let mut calculator = mortgage(360, Engine::EventDriven);
+        calculator.set_compounding_strategy(*strategy);
+        group.bench_with_input(
+            BenchmarkId::new(format!("{:?}", strategy), 360),
+            &calculator,
+            |b, calculator| b.iter(|| calculator.clone().compute().unwrap()),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, engines, extra_payments, compounding_strategies);
+criterion_main!(benches);
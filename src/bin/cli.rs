@@ -2,13 +2,15 @@
 
 use interest_calculator::*;
 
-use chrono::{Datelike, Month, NaiveDate};
+use chrono::{Datelike, Month, NaiveDate, Weekday};
 use num_traits::FromPrimitive;
+use plotters::prelude::*;
+use std::path::{Path, PathBuf};
 use structopt::StructOpt;
 
 #[derive(Debug, StructOpt)]
 #[structopt(name = "interest-calculator")]
-struct Opt {
+pub struct Opt {
     /// Total sum of the loan.
     #[structopt(long, default_value = "4350000")]
     loan: i64,
@@ -36,6 +38,62 @@ struct Opt {
     #[structopt(short, long, default_value = "45")]
     fee: i32,
 
+    /// A residual amount due with the last term, either an amount or a percentage
+    /// of the loan when suffixed by `%`.
+    #[structopt(long)]
+    balloon: Option<Balloon>,
+    /// The number of terms at the start of the loan where only interest and fees are payed.
+    /// These terms are part of the total number of terms.
+    #[structopt(long, default_value = "0")]
+    interest_only_terms: u32,
+    /// How due dates on weekends and Norwegian holidays are moved to a business day,
+    /// either `none`, `following`, `modified-following` or `preceding`.
+    #[structopt(long, default_value = "none")]
+    business_day_convention: BusinessDayConvention,
+    /// The weekday installments are due on, when there are 26 or 52 terms per year.
+    #[structopt(long, parse(try_from_str = parse_weekday))]
+    due_weekday: Option<Weekday>,
+    /// Repay the same portion of the loan every term, rather than the same term payment.
+    #[structopt(long)]
+    serial: bool,
+    /// How extra payments affect the remaining terms, either `shorten-term` or `lower-payment`.
+    #[structopt(long, default_value = "shorten-term")]
+    extra_policy: ExtraInstallmentPolicy,
+    /// When the accrued interest is posted to the outstanding loan, either `on-installment`,
+    /// `daily`, `end-of-month` or `end-of-year`. Overrides the one of a scenario, when given.
+    #[structopt(long)]
+    compounding: Option<CompoundingStrategy>,
+    /// The last date of a fixed nominal interest, after which the floating rates apply.
+    #[structopt(long)]
+    fixed_until: Option<NaiveDate>,
+    /// Floating nominal interest rates, each effective from its date,
+    /// formatted as `yyyy-mm-dd=rate` and separated by commas.
+    #[structopt(long, use_delimiter = true, parse(try_from_str = parse_dated_rate))]
+    floating_rates: Vec<(NaiveDate, f64)>,
+    /// Treat the floating rates as a reference index, where the loan pays this margin above it.
+    #[structopt(long)]
+    margin: Option<f64>,
+    /// Read the reference index from a CSV file with a `date,rate` header,
+    /// where the loan pays the margin above it.
+    #[structopt(long, conflicts_with("floating-rates"))]
+    index: Option<PathBuf>,
+    /// How often the nominal interest is reset to the reference index, such as `monthly`
+    /// or `quarterly`.
+    #[structopt(long, default_value = "monthly")]
+    reset: RecurringInterval,
+    /// The number of days before each reset the reference index is observed.
+    #[structopt(long, default_value = "0")]
+    lookback: u32,
+    /// The highest nominal interest of the loan.
+    #[structopt(long)]
+    cap: Option<f64>,
+    /// The lowest nominal interest of the loan.
+    #[structopt(long)]
+    floor: Option<f64>,
+    /// The largest change of the nominal interest on a single reset, in percentage points.
+    #[structopt(long)]
+    max_rate_change: Option<f64>,
+
     /// The number of terms to perform extra downpayment on
     #[structopt(long, default_value = "0")]
     extra_terms: u32,
@@ -45,14 +103,63 @@ struct Opt {
     /// The amount to inject as extra downpayment in a term.
     #[structopt(long, default_value = "6000")]
     extra_amount: i32,
+
+    /// Read the loan and all of its events from a scenario file, as exported by the GUI.
+    /// The loan options are ignored when a scenario is given.
+    #[structopt(long)]
+    scenario: Option<PathBuf>,
+
+    /// Quote the amount required to repay the entire loan on this date, as yyyy-mm-dd.
+    #[structopt(long)]
+    payoff_quote: Option<NaiveDate>,
+
+    /// Render a stacked bar chart of the yearly payment breakdown to this SVG file.
+    #[structopt(long)]
+    yearly_chart: Option<PathBuf>,
+
+    /// Write the cash flows of every calendar month to this CSV file.
+    #[structopt(long)]
+    cash_flow: Option<PathBuf>,
+
+    /// Print the interest, fees and principal payed every calendar year, by every lender,
+    /// along with the balance on 31 Dec.
+    #[structopt(long)]
+    tax_statement: bool,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
 }
 
-pub fn parse() -> Result<(NaiveDate, LoanInitialization), String> {
-    let opt = Opt::from_args();
+#[derive(Debug, StructOpt)]
+pub enum Command {
+    /// Compute every loan of a portfolio, along with the totals of the portfolio.
+    /// The loan options are ignored.
+    Portfolio {
+        /// Read the portfolio from this CSV file with a loan on every line when ending with
+        /// `.csv`, or from a JSON list of scenarios otherwise.
+        file: PathBuf,
+        /// The date the outstanding loans are reported at, as yyyy-mm-dd. Today, unless given.
+        #[structopt(long)]
+        as_of: Option<NaiveDate>,
+    },
+}
+
+fn parse_dated_rate(s: &str) -> Result<(NaiveDate, f64), String> {
+    match parse_dated_rates(s)?.as_slice() {
+        [rate] => Ok(*rate),
+        _ => Err("expecting a rate formatted as yyyy-mm-dd=rate".to_string()),
+    }
+}
+
+fn parse_weekday(s: &str) -> Result<Weekday, String> {
+    s.parse()
+        .map_err(|_| "expecting a weekday, such as mon or monday".to_string())
+}
 
+pub fn parse(opt: &Opt) -> Result<(NaiveDate, LoanInitialization), String> {
     // Sanify how many terms_per_year we can do
     // I think its safe to assume that only a few combinations make sense
-    const ALLOWED_TERMS_PER_YEAR: [u32; 6] = [1, 2, 3, 4, 6, 12];
+    const ALLOWED_TERMS_PER_YEAR: [u32; 8] = [1, 2, 3, 4, 6, 12, 26, 52];
     if !ALLOWED_TERMS_PER_YEAR.contains(&opt.terms_per_year) {
         return Err(format!(
             "error: The argument '--terms-per-year <num>' must be one of {:?}",
@@ -74,6 +181,8 @@ pub fn parse() -> Result<(NaiveDate, LoanInitialization), String> {
         4 => TermsPerYear::Four,
         6 => TermsPerYear::Six,
         12 => TermsPerYear::Twelve,
+        26 => TermsPerYear::TwentySix,
+        52 => TermsPerYear::FiftyTwo,
         _ => panic!("cannot be reached"),
     };
 
@@ -90,32 +199,215 @@ pub fn parse() -> Result<(NaiveDate, LoanInitialization), String> {
     let initial = LoanInitialization {
         loan: opt.loan as f64,
         nominal_interest: opt.interest,
-        administration_fee: 0.0,
         installment_fee: opt.fee as f64,
-
-        terms: terms,
+        terms,
         terms_per_year,
         due_within_month: MonthlyDueDate::Date(term_due_day),
         first_installment_month: month,
+        business_day_convention: opt.business_day_convention,
+        due_weekday: opt.due_weekday,
+        balloon: opt.balloon,
+        interest_only_terms: opt.interest_only_terms,
+        amortisation: if opt.serial {
+            Amortisation::Serial
+        } else {
+            Amortisation::Annuity
+        },
+        extra_policy: opt.extra_policy,
+        fixed_until: opt.fixed_until,
+        floating_rate: match (&opt.index, opt.margin) {
+            (Some(path), margin) => Some(FloatingRate::Indexed(IndexedRate {
+                index: IndexSeries::load(path)?,
+                margin: margin.unwrap_or(0.0),
+                reset: opt.reset,
+                lookback_days: opt.lookback,
+            })),
+            (None, _) if opt.floating_rates.is_empty() => None,
+            (None, Some(margin)) => Some(FloatingRate::Indexed(IndexedRate {
+                index: IndexSeries::new(opt.floating_rates.clone()),
+                margin,
+                reset: opt.reset,
+                lookback_days: opt.lookback,
+            })),
+            (None, None) => Some(FloatingRate::Path(opt.floating_rates.clone())),
+        },
+        rate_limits: RateLimits {
+            cap: opt.cap,
+            floor: opt.floor,
+            max_change: opt.max_rate_change,
+        },
+        ..LoanInitialization::default()
     };
 
+    if opt.margin.is_some() && initial.floating_rate.is_none() {
+        return Err(
+            "error: The argument '--margin <margin>' requires '--floating-rates' or '--index'"
+                .to_string(),
+        );
+    }
+
     Ok((date, initial))
 }
 
 fn main() {
-    #[cfg(wasm)]
-    panic::set_hook(Box::new(console_error_panic_hook::hook));
+    #[cfg(target_arch = "wasm32")]
+    std::panic::set_hook(Box::new(console_error_panic_hook::hook));
 
-    let (date, initial) = match parse() {
-        Ok(initial) => initial,
+    let opt = Opt::from_args();
+    if let Some(Command::Portfolio { file, as_of }) = opt.command.as_ref() {
+        let as_of = as_of.unwrap_or_else(|| chrono::offset::Utc::today().naive_utc());
+        match Portfolio::load(file).and_then(|p| p.compute(as_of)) {
+            Ok(result) => print_portfolio(&result),
+            Err(e) => eprintln!("{}", e),
+        }
+        return;
+    }
+
+    let calculator = match opt.scenario.as_ref() {
+        Some(path) => Scenario::load(path).and_then(|s| s.to_calculator()),
+        None => parse(&opt).map(|(date, initial)| InteractiveCalculator::new(date, initial)),
+    };
+    let mut calculator = match calculator {
+        Ok(calculator) => calculator,
         Err(e) => {
             eprintln!("{}", e);
             return;
         }
     };
+    if let Some(strategy) = opt.compounding {
+        calculator.set_compounding_strategy(strategy);
+    }
 
-    let calculator = InteractiveCalculator::new(date, initial);
     let total = calculator.compute();
 
     println!("{:#?}", total);
+
+    if let Some(date) = opt.payoff_quote {
+        println!("{:#?}", calculator.payoff_quote(date));
+    }
+
+    if let Some(path) = opt.yearly_chart.as_ref() {
+        let yearly = match calculator.ledger() {
+            Ok(ledger) => YearlyResult::from_ledger(&ledger),
+            Err(e) => {
+                eprintln!("{}", e);
+                return;
+            }
+        };
+        let root = SVGBackend::new(path, (1240, 768)).into_drawing_area();
+        if let Err(e) = draw_yearly_breakdown(&root, &yearly) {
+            eprintln!("{}", e);
+        }
+    }
+
+    if opt.tax_statement {
+        match calculator.ledger() {
+            Ok(ledger) => print_tax_statements(&TaxStatement::from_ledger(&ledger)),
+            Err(e) => eprintln!("{}", e),
+        }
+    }
+
+    if let Some(path) = opt.cash_flow.as_ref() {
+        let written = calculator
+            .ledger()
+            .and_then(|ledger| write_cash_flow(path, &MonthlyCashFlow::from_ledger(&ledger)));
+        if let Err(e) = written {
+            eprintln!("{}", e);
+        }
+    }
+}
+
+fn print_tax_statements(statements: &[TaxStatement]) {
+    println!(
+        "{:<6} {:>6} {:>10} {:>10} {:>14} {:>10} {:>14} {:>16}",
+        "Year", "Lender", "From", "To", "Interest", "Fees", "Principal", "Balance"
+    );
+    for statement in statements.iter() {
+        for period in statement.lenders.iter() {
+            println!(
+                "{:<6} {:>6} {:>10} {:>10} {:>14.2} {:>10.2} {:>14.2} {:>16.2}",
+                statement.year,
+                period.lender,
+                period.first_date.format("%d.%m"),
+                period.last_date.format("%d.%m"),
+                period.interest,
+                period.fee,
+                period.principal,
+                period.closing_balance
+            );
+        }
+    }
+}
+
+fn write_cash_flow(path: &Path, monthly: &[MonthlyCashFlow]) -> Result<(), String> {
+    let failed = |e: csv::Error| format!("failed to write {}: {}", path.display(), e);
+    let mut writer = csv::Writer::from_path(path).map_err(failed)?;
+    writer
+        .write_record([
+            "month",
+            "disbursed",
+            "interest",
+            "principal",
+            "fee",
+            "extra",
+            "closing_balance",
+        ])
+        .map_err(failed)?;
+    for m in monthly.iter() {
+        writer
+            .write_record([
+                format!("{}-{:02}", m.year, m.month),
+                format!("{:.2}", m.disbursed),
+                format!("{:.2}", m.interest),
+                format!("{:.2}", m.principal),
+                format!("{:.2}", m.fee),
+                format!("{:.2}", m.extra),
+                format!("{:.2}", m.closing_outstanding_loan),
+            ])
+            .map_err(failed)?;
+    }
+    writer
+        .flush()
+        .map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+fn print_portfolio(result: &PortfolioResult) {
+    println!(
+        "{:<24} {:>16} {:>8} {:>12} {:>16}",
+        "Loan", "Outstanding", "Rate", "Matures", "Total interest"
+    );
+    for loan in result.loans.iter() {
+        println!(
+            "{:<24} {:>16.2} {:>8.3} {:>12} {:>16.2}",
+            loan.name,
+            loan.outstanding(),
+            loan.snapshot.as_ref().map_or(0.0, |s| s.nominal_interest),
+            loan.summary.end_date,
+            loan.summary.total_interest
+        );
+    }
+
+    println!();
+    println!(
+        "Outstanding as of {}: {:.2}",
+        result.as_of, result.total_outstanding
+    );
+    println!("Weighted average rate: {:.3}", result.weighted_average_rate);
+
+    println!();
+    println!("{:<6} {:>16} {:>16}", "Year", "Interest income", "Maturing");
+    let years: std::collections::BTreeSet<i32> = result
+        .interest_income
+        .keys()
+        .chain(result.maturity_profile.keys())
+        .copied()
+        .collect();
+    for year in years {
+        println!(
+            "{:<6} {:>16.2} {:>16.2}",
+            year,
+            result.interest_income.get(&year).unwrap_or(&0.0),
+            result.maturity_profile.get(&year).unwrap_or(&0.0)
+        );
+    }
 }
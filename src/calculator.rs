@@ -1,57 +1,63 @@
 //! Implementation of the public API to consume the calculations.
 
+use crate::calendar::{BusinessDayConvention, HolidayCalendar, NorwegianCalendar};
 use crate::events::*;
 use crate::reports::*;
 
-use chrono::{Datelike, Month, NaiveDate};
+use chrono::{Datelike, Duration, Month, NaiveDate};
 use num_traits::FromPrimitive;
-use std::collections::{BTreeMap, VecDeque};
-
-#[derive(Debug)]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::str::FromStr;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Outstanding amounts below this are rounding residue of the interest accrual,
+/// and are settled by the installment rather than left for another term.
+const ROUNDING_RESIDUE: f64 = 1e-6;
+
+/// When the accrued interest is posted to the outstanding loan.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum CompoundingStrategy {
+    /// Every day, such that interest accrues on the interest of previous days.
     Daily,
+    /// On every installment, where the interest is payed as part of the installment.
+    #[default]
     OnInstallment,
+    /// On the last day of every month.
     EndOfMonth,
+    /// On the last day of every year.
     EndOfYear,
 }
 
-#[derive(Debug)]
-enum NotableEvents {
-    Initialization(f64),
-    RepaymentInstallment(f64),
-    InterestOnlyInstallment(f64),
-    ExtraInstallment(f64),
-}
-
-/// The daily result produced by a Calculator.
-/// All fields here represent the state on the date of the status report.
-#[derive(Debug)]
-struct Daily {
-    /// The date of this Daily status report.
-    pub date: NaiveDate,
-
-    /// The amount of interest accrued on this date.
-    pub accrued_interest: f64,
-    /// If any interest was compounded into the principal loan, this is represented here.
-    pub compounded_interest: f64,
-    /// The amount disbursed on the loan.
-    pub disbursed: f64,
-    /// The total number of fees accrued
-    pub fee: f64,
-    /// If anything was repayed to the loan, it is reflected in this status.
-    pub repayed: f64,
-    /// The portion of the repayed status that is an ordrinary repayment portion.
-    pub repayment_installment: f64,
-    /// The portion of the repayed status that is the accrued interest portion.
-    pub interest_installment: f64,
-    /// The portion of the repayed status that is due to extraordinary installment.
-    pub extra_installment: f64,
-
-    /// Notable events that occurred on this date.
-    pub notable_events: Vec<NotableEvents>,
-}
-
-#[derive(Debug, Eq, PartialEq)]
+impl FromStr for CompoundingStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "daily" => Ok(CompoundingStrategy::Daily),
+            "on-installment" => Ok(CompoundingStrategy::OnInstallment),
+            "end-of-month" => Ok(CompoundingStrategy::EndOfMonth),
+            "end-of-year" => Ok(CompoundingStrategy::EndOfYear),
+            _ => Err(
+                "expecting either daily, on-installment, end-of-month or end-of-year".to_string(),
+            ),
+        }
+    }
+}
+
+/// How the calculation steps through the lifetime of the loan.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Engine {
+    /// Jump from one significant date to the next, such as installments, compounding
+    /// and events, accruing the interest of the quiet days in between in closed form.
+    #[default]
+    EventDriven,
+    /// Process every single day of the loan.
+    Daily,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
 enum InstallmentType {
     InterestOnly,
     Repayment,
@@ -59,7 +65,7 @@ enum InstallmentType {
 
 /// The operations that can occur on a single day.
 /// Multiple actions can be specified.
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 struct DayActions {
     /// An administration cost to the initialization of the installment loan.
     initialization: Option<(f64, f64)>,
@@ -71,13 +77,95 @@ struct DayActions {
     /// Do we have any installment action for this day?
     installment: Option<InstallmentType>,
     /// An additional repayment on the principal loan.
-    extra_installments: Vec<f64>,
+    extra_installments: Vec<ScheduledExtra>,
+    /// The nominal interest is changed, effective from this day.
+    interest_change: Option<f64>,
+    /// An increase of the principal loan, with an administration fee.
+    refinance: Option<(f64, f64)>,
+    /// The loan is transferred to another bank, with an administration fee,
+    /// and any fee charged by the previous bank for the early repayment.
+    transfer: Option<(f64, Option<PrepaymentFee>)>,
+    /// The entire loan is repayed ahead of its schedule, with an administration fee
+    /// and any fee charged for the early repayment.
+    payoff: Option<(f64, Option<PrepaymentFee>)>,
+}
+
+/// A single extra installment scheduled on a future date.
+#[derive(Clone, Copy, Debug)]
+struct ScheduledExtra {
+    amount: f64,
+    /// How the extra installment affects the remaining terms.
+    policy: ExtraInstallmentPolicy,
+    /// The fee charged by the bank for the extra installment.
+    prepayment_fee: Option<PrepaymentFee>,
+}
+
+impl DayActions {
+    /// Merge the event driven actions of other into this set of actions.
+    fn merge_events(&mut self, other: DayActions) {
+        self.extra_installments.extend(other.extra_installments);
+        self.interest_change = other.interest_change.or(self.interest_change);
+        self.refinance = other.refinance.or(self.refinance);
+        self.transfer = other.transfer.or(self.transfer);
+        self.payoff = other.payoff.or(self.payoff);
+    }
+}
+
+/// The actions scheduled on future dates, consumed as their date approaches.
+///
+/// The actions are shared between the checkpoints of a calculation,
+/// each with their own position.
+#[derive(Clone, Debug)]
+struct ScheduledActions {
+    actions: Arc<Vec<(NaiveDate, DayActions)>>,
+    /// The position of the next action to consume.
+    next: usize,
+}
+
+impl ScheduledActions {
+    fn new(actions: VecDeque<(NaiveDate, DayActions)>) -> Self {
+        ScheduledActions {
+            actions: Arc::new(Vec::from(actions)),
+            next: 0,
+        }
+    }
+
+    /// The date of the next action to consume, if any.
+    fn next_date(&self) -> Option<NaiveDate> {
+        self.actions.get(self.next).map(|(date, _)| *date)
+    }
+
+    /// Returns Ok(Some(...)) if actions array has actions for this date.
+    /// Returns Ok(None) if there are no actions for this date
+    /// Return Err(...) on fatal error (logic break)
+    fn fetch(&mut self, date: NaiveDate) -> Result<Option<DayActions>, String> {
+        match self.actions.get(self.next) {
+            // Date in the future. Continue with none
+            Some((action_date, _)) if action_date > &date => Ok(None),
+            Some((action_date, _)) if action_date < &date => Err(
+                "action events where in the past - this means we skipped some events".to_string(),
+            ),
+            Some((_, actions)) => {
+                self.next += 1;
+                Ok(Some(actions.clone()))
+            }
+            // The actions set is empty
+            // This is effectively a condition where we cannot terminate
+            None => {
+                Err("no more daily actions, yet we have not terminated computation".to_string())
+            }
+        }
+    }
 }
 
 /// This structure is used to hold the current state of a calculation.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 struct CurrentCalculationState {
     interest_compounding_strategy: CompoundingStrategy,
+    /// Whether every day is processed, or only the significant dates.
+    engine: Engine,
+    /// The last date interest has been accrued for.
+    interest_accrued_until: NaiveDate,
 
     /// This is total number of terms we plan to to repay the full loan
     /// where each scheduled installment contains a repayment portion.
@@ -91,6 +179,22 @@ struct CurrentCalculationState {
     planned_repayment_terms: u32,
     /// Total number of scheduled repayment installments.
     completed_repayment_terms: u32,
+    /// The number of upcoming scheduled installments that are frozen,
+    /// where only the interest portion is payed.
+    pending_repayment_freezes: u32,
+    /// The number of upcoming interest-only terms at the start of the loan,
+    /// where the interest portion and the installment fee is payed.
+    pending_interest_only_terms: u32,
+    /// Extra installments scheduled on future dates.
+    scheduled_extra_installments: BTreeMap<NaiveDate, Vec<ScheduledExtra>>,
+
+    /// How the loan is amortised over the repayment terms.
+    amortisation: Amortisation,
+    /// The policy of extra installments that do not set their own.
+    extra_policy: ExtraInstallmentPolicy,
+
+    /// The residual amount of the loan due with the last term.
+    balloon: f64,
 
     /// The original principal loan sum used to calculate the basis of the loan.
     /// This reflects the amount disbursed, including fees, and is used to compute term payments.
@@ -110,32 +214,199 @@ struct CurrentCalculationState {
     current_terms_per_year: TermsPerYear,
     current_monthly_due_day: MonthlyDueDate,
 
+    /// How due dates are moved to business days of the holiday calendar.
+    business_day_convention: BusinessDayConvention,
+    holiday_calendar: Arc<dyn HolidayCalendar + Send + Sync>,
+
+    /// The last date of the fixed nominal interest, if any.
+    fixed_until: Option<NaiveDate>,
+    /// Contractual limits applied on every change of the nominal interest.
+    rate_limits: RateLimits,
+
     /// This is the computed date for our next installment, regardless of type.
     /// Calculated based external date factor, terms per year and monthly due date.
     /// This value is re-calculated on in several day actions or loan events.
+    ///
+    /// The installment is payed on this date, moved to a business day by the
+    /// business day convention.
     computed_installment_date: NaiveDate,
 
     /// Computed effective interest. Recomputed when any of its parameters change.
     computed_effective_interest: f64,
-    /// The computed term payment.
+    /// The computed term payment. For serial loans, this is the repayment portion
+    /// of each term, excluding the interest.
     computed_term_payment: f64,
+
+    /// Whether the loan is repayed in full, and the calculation has ended.
+    finished: bool,
+}
+
+/// The calculations of a loan, the loan itself along with the variants it is compared to.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+enum Calculation {
+    Loan,
+    WithoutExtra,
+    WithoutRateLimits,
+}
+
+/// The calculation state at the start of a date, from which a later calculation may resume.
+#[derive(Clone, Debug)]
+struct Checkpoint {
+    state: CurrentCalculationState,
+    actions: ScheduledActions,
+    /// The number of days processed prior to the checkpoint.
+    processed: usize,
+}
+
+/// The checkpoints and results of a previous calculation.
+#[derive(Debug, Default)]
+struct CachedCalculation {
+    /// Checkpoints ordered by date, all of them valid for the current events.
+    checkpoints: BTreeMap<NaiveDate, Checkpoint>,
+    /// Every processed day of the previous calculation,
+    /// valid up to the days processed prior to any checkpoint.
+    dailys: Vec<Daily>,
+    /// The final state of the previous calculation, if none of the events have changed since.
+    complete: Option<CurrentCalculationState>,
+}
+
+/// The checkpoints of previous calculations, such that the calculation after an edit
+/// only has to resume from the earliest changed date.
+///
+/// A cloned calculator starts with an empty cache.
+#[derive(Debug, Default)]
+struct CalculationCache(Mutex<HashMap<Calculation, CachedCalculation>>);
+
+impl Clone for CalculationCache {
+    fn clone(&self) -> Self {
+        CalculationCache::default()
+    }
+}
+
+impl CalculationCache {
+    fn lock(&self) -> MutexGuard<'_, HashMap<Calculation, CachedCalculation>> {
+        // A poisoned cache only holds checkpoints that were valid before the panic.
+        self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// The result of the previous calculation, if none of the events have changed since.
+    fn complete(&self, calculation: Calculation) -> Option<(Vec<Daily>, CurrentCalculationState)> {
+        let cache = self.lock();
+        let cached = cache.get(&calculation)?;
+        let state = cached.complete.as_ref()?;
+        Some((cached.dailys.clone(), state.clone()))
+    }
+
+    /// The latest valid checkpoint, no later than the date by, with every day processed
+    /// prior to it.
+    fn resume(
+        &self,
+        calculation: Calculation,
+        by: Option<NaiveDate>,
+    ) -> Option<(NaiveDate, Checkpoint, Vec<Daily>)> {
+        let cache = self.lock();
+        let cached = cache.get(&calculation)?;
+        let (date, checkpoint) = match by {
+            Some(by) => cached.checkpoints.range(..=by).next_back(),
+            None => cached.checkpoints.iter().next_back(),
+        }?;
+        let dailys = cached.dailys[..checkpoint.processed].to_vec();
+        Some((*date, checkpoint.clone(), dailys))
+    }
+
+    /// Keep the checkpoints and the result of a complete calculation.
+    fn store(
+        &self,
+        calculation: Calculation,
+        checkpoints: Vec<(NaiveDate, Checkpoint)>,
+        dailys: &[Daily],
+        state: &CurrentCalculationState,
+    ) {
+        let mut cache = self.lock();
+        let cached = cache.entry(calculation).or_default();
+        cached.checkpoints.extend(checkpoints);
+        cached.dailys = dailys.to_vec();
+        cached.complete = Some(state.clone());
+    }
+
+    /// Discard every checkpoint and result affected by a change of the events on date.
+    /// A checkpoint on the date itself is discarded as well, since the event-driven
+    /// engine may no longer stop on the date without its events.
+    fn invalidate(&mut self, date: NaiveDate) {
+        let cache = self.0.get_mut().unwrap_or_else(|e| e.into_inner());
+        for cached in cache.values_mut() {
+            cached.checkpoints.split_off(&date);
+            cached.complete = None;
+        }
+    }
 }
 
 /// This is an interactive structure used to construct and alter the events
 /// within an installment loan calculations.
+#[derive(Clone)]
 pub struct InteractiveCalculator {
     /// The set of events, ordered by date.
     /// The first element is guaranteed to be LoanEvent::Initial,
     /// meaning no later element may have a date prior to the LoanEvent::Initial date.
     events: BTreeMap<NaiveDate, Vec<LoanEvent>>,
+    /// The holiday calendar due dates are moved by, according to the business day
+    /// convention of the loan.
+    holiday_calendar: Arc<dyn HolidayCalendar + Send + Sync>,
+    /// How the calculation steps through the lifetime of the loan.
+    engine: Engine,
+    /// When the accrued interest is posted to the outstanding loan.
+    compounding_strategy: CompoundingStrategy,
+    /// The checkpoints of previous calculations, invalidated by every change of the events.
+    cache: CalculationCache,
 }
 
 impl InteractiveCalculator {
     /// Construct a new InteractiveCalculator with the initial loan event.
+    ///
+    /// Due dates are moved by the Norwegian holiday calendar,
+    /// see [`InteractiveCalculator::set_holiday_calendar`].
     pub fn new(date: NaiveDate, initial: LoanInitialization) -> Self {
         let mut map = BTreeMap::new();
         map.insert(date, vec![LoanEvent::Initial(initial)]);
-        InteractiveCalculator { events: map }
+        InteractiveCalculator {
+            events: map,
+            holiday_calendar: Arc::new(NorwegianCalendar),
+            engine: Engine::default(),
+            compounding_strategy: CompoundingStrategy::default(),
+            cache: CalculationCache::default(),
+        }
+    }
+
+    /// Replace the holiday calendar due dates are moved by, according to the
+    /// business day convention of the loan.
+    pub fn set_holiday_calendar<C>(&mut self, calendar: C)
+    where
+        C: HolidayCalendar + Send + Sync + 'static,
+    {
+        self.holiday_calendar = Arc::new(calendar);
+        self.cache.invalidate(self.disbursement_date());
+    }
+
+    /// Replace how the calculation steps through the lifetime of the loan.
+    ///
+    /// Both engines compute the same results, where the daily engine includes
+    /// every day of the loan in the ledger.
+    pub fn set_engine(&mut self, engine: Engine) {
+        self.engine = engine;
+        self.cache.invalidate(self.disbursement_date());
+    }
+
+    /// Replace when the accrued interest is posted to the outstanding loan.
+    ///
+    /// The interest is compounded on every installment by default.
+    pub fn set_compounding_strategy(&mut self, strategy: CompoundingStrategy) {
+        self.compounding_strategy = strategy;
+        self.cache.invalidate(self.disbursement_date());
+    }
+
+    /// When the accrued interest is posted to the outstanding loan.
+    pub fn compounding_strategy(&self) -> CompoundingStrategy {
+        self.compounding_strategy
     }
 
     /// Add an extra installment event to the calculator.
@@ -149,32 +420,440 @@ impl InteractiveCalculator {
             amount: extra.amount,
             count: std::num::NonZeroU32::new(1).unwrap(),
             recurring_interval: RecurringInterval::Monthly,
+            policy: extra.policy,
+            prepayment_fee: extra.prepayment_fee,
         };
 
         self.add_event_extra_recurring(date, extra)
     }
 
-    /// Add an extra installment event to the calculator.
-    // TODO: Make this public once we've implemented it properly
-    fn add_event_extra_recurring(
+    /// Add a set of recurring extra installments to the calculator.
+    /// The first extra installment is payed on the date of the event.
+    pub fn add_event_extra_recurring(
         &mut self,
         date: NaiveDate,
         extra: LoanRecurringExtraInstallments,
     ) -> Result<(), String> {
-        // TODO: Sanity check date
+        self.add_event(date, LoanEvent::Extra(extra))
+    }
+
+    /// Add an interest change event to the calculator.
+    pub fn add_event_interest_change(
+        &mut self,
+        date: NaiveDate,
+        change: LoanInterestChange,
+    ) -> Result<(), String> {
+        self.add_event(date, LoanEvent::InterestChange(change))
+    }
+
+    /// Add a bank transfer event to the calculator.
+    pub fn add_event_bank_transfer(
+        &mut self,
+        date: NaiveDate,
+        transfer: LoanTransfer,
+    ) -> Result<(), String> {
+        self.add_event(date, LoanEvent::BankTransfer(transfer))
+    }
+
+    /// Add a refinance event to the calculator.
+    pub fn add_event_refinance(
+        &mut self,
+        date: NaiveDate,
+        refinance: LoanRefinance,
+    ) -> Result<(), String> {
+        self.add_event(date, LoanEvent::Refinance(refinance))
+    }
+
+    /// Add a repayment freeze event to the calculator.
+    /// The freeze applies to the next scheduled installments from the date of the event.
+    pub fn add_event_repayment_freeze(
+        &mut self,
+        date: NaiveDate,
+        freeze: LoanRepaymentFreeze,
+    ) -> Result<(), String> {
+        self.add_event(date, LoanEvent::RepaymentFreeze(freeze))
+    }
+
+    /// Add an early payoff event to the calculator, ending the loan on the date of the event.
+    pub fn add_event_early_payoff(
+        &mut self,
+        date: NaiveDate,
+        payoff: LoanPayoff,
+    ) -> Result<(), String> {
+        self.add_event(date, LoanEvent::EarlyPayoff(payoff))
+    }
+
+    /// Insert any event after the disbursement date.
+    pub(crate) fn add_event(&mut self, date: NaiveDate, event: LoanEvent) -> Result<(), String> {
+        if let LoanEvent::Initial(_) = event {
+            return Err("a loan can only have a single initial loan event".to_string());
+        }
+        if let LoanEvent::InterestChange(change) = &event {
+            change.validate()?;
+        }
+        if date <= self.disbursement_date() {
+            return Err("event date must be after the disbursement date".to_string());
+        }
+
+        self.cache.invalidate(date);
+        self.events.entry(date).or_default().push(event);
+        Ok(())
+    }
+
+    /// The date of the initial loan event.
+    pub fn disbursement_date(&self) -> NaiveDate {
+        // SAFETY(unwrap): events always contains the initial event.
+        *self.events.keys().next().unwrap()
+    }
+
+    /// Iterate over all events, ordered by date.
+    /// Events on the same date are ordered by insertion.
+    ///
+    /// The position of an event in this iterator is the index used to
+    /// modify or remove the event.
+    pub fn events(&self) -> impl Iterator<Item = (NaiveDate, &LoanEvent)> {
+        self.events
+            .iter()
+            .flat_map(|(date, events)| events.iter().map(move |e| (*date, e)))
+    }
+
+    /// Retrieve the event at index, as ordered by [`InteractiveCalculator::events`].
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn event(&self, index: usize) -> (NaiveDate, &LoanEvent) {
+        self.events().nth(index).expect("event index out of bounds")
+    }
+
+    /// Retrieve a mutable reference to the event at index,
+    /// as ordered by [`InteractiveCalculator::events`].
+    ///
+    /// Every calculation from the date of the event is discarded, as the event may be changed
+    /// through the reference. Use [`InteractiveCalculator::event`] to only read the event.
+    ///
+    /// Panics if the index is out of bounds.
+    pub fn event_index(&mut self, index: usize) -> (NaiveDate, &mut LoanEvent) {
+        // The event may be changed through the reference, affecting calculations from its date.
+        if let Ok((date, _)) = self.event_position(index) {
+            self.cache.invalidate(date);
+        }
         self.events
-            .entry(date)
-            .and_modify(|e| e.push(LoanEvent::Extra(extra.clone())))
-            .or_insert(vec![LoanEvent::Extra(extra)]);
+            .iter_mut()
+            .flat_map(|(date, events)| events.iter_mut().map(move |e| (*date, e)))
+            .nth(index)
+            .expect("event index out of bounds")
+    }
+
+    /// Move the event at index to another date.
+    ///
+    /// The initial loan event must remain prior to all other events,
+    /// and all other events must remain after the disbursement date.
+    pub fn change_event_date(&mut self, index: usize, date: NaiveDate) -> Result<(), String> {
+        let (current, position) = self.event_position(index)?;
+        if current == date {
+            return Ok(());
+        }
+
+        if index == 0 {
+            let first_event = self.events.keys().nth(1);
+            if first_event.is_some_and(|first| date >= *first) {
+                return Err(
+                    "disbursement date must be prior to all other events on the loan".to_string(),
+                );
+            }
+        } else if date <= self.disbursement_date() {
+            return Err("event date must be after the disbursement date".to_string());
+        }
+
+        self.cache.invalidate(current.min(date));
+        let event = self.take_event(current, position);
+        self.events.entry(date).or_default().push(event);
         Ok(())
     }
 
+    /// Remove the event at index from the calculator.
+    /// The initial loan event cannot be removed.
+    pub fn remove_event(&mut self, index: usize) -> Result<LoanEvent, String> {
+        if index == 0 {
+            return Err("the initial loan event cannot be removed".to_string());
+        }
+        let (date, position) = self.event_position(index)?;
+        self.cache.invalidate(date);
+        Ok(self.take_event(date, position))
+    }
+
+    /// Resolve the index of an event into its date and position within that date.
+    fn event_position(&self, index: usize) -> Result<(NaiveDate, usize), String> {
+        let mut remaining = index;
+        for (date, events) in self.events.iter() {
+            if remaining < events.len() {
+                return Ok((*date, remaining));
+            }
+            remaining -= events.len();
+        }
+        Err(format!("no event with index {}", index))
+    }
+
+    /// Remove the event on date at position, cleaning up empty dates.
+    fn take_event(&mut self, date: NaiveDate, position: usize) -> LoanEvent {
+        // SAFETY(unwrap): guarded by event_position.
+        let events = self.events.get_mut(&date).unwrap();
+        let event = events.remove(position);
+        if events.is_empty() {
+            self.events.remove(&date);
+        }
+        event
+    }
+
     /// Compute the installment loan result for the lifetime of the loan based on current events.
     pub fn compute(&self) -> Result<TotalResult, String> {
-        let mut events_iter = self.events.iter();
+        let (dailys, state) = self.compute_dailys()?;
+        let total_interest = dailys.iter().map(|x| x.compounded_interest).sum();
+        let first_installment = dailys.iter().find(|x| x.repayment_installment > 0.0);
+
+        Ok(TotalResult {
+            total_cost: dailys.iter().map(|x| x.repayed).sum(),
+            total_loan: dailys.iter().map(|x| x.disbursed).sum(),
+            total_repayment_installment: dailys.iter().map(|x| x.repayment_installment).sum(),
+            total_extra_installment: dailys.iter().map(|x| x.extra_installment).sum(),
+            total_balloon_installment: dailys.iter().map(|x| x.balloon_installment).sum(),
+            total_interest,
+            total_fee: dailys.iter().map(|x| x.fee).sum(),
+            total_prepayment_fee: dailys.iter().map(|x| x.prepayment_fee).sum(),
+            total_payoff_installment: dailys.iter().map(|x| x.payoff_installment).sum(),
+
+            term_payment: first_installment.map_or(0.0, |x| {
+                x.repayment_installment + x.interest_installment + x.installment_fee
+            }),
+            term_payment_after_fixed_rate: state.fixed_until.and_then(|until| {
+                dailys
+                    .iter()
+                    .find(|x| x.date > until && x.repayment_installment > 0.0)
+                    .map(|x| x.repayment_installment + x.interest_installment + x.installment_fee)
+            }),
+            effective_annual_rate: effective_annual_rate(&dailys).ok(),
+
+            disbursement_date: dailys.first().unwrap().date,
+            first_installment_date: first_installment
+                .map_or(NaiveDate::from_ymd(1970, 1, 1), |x| x.date),
+            end_date: dailys.last().unwrap().date,
+            planned_terms: state.planned_repayment_terms as i32,
+            completed_terms: state.completed_repayment_terms as i32,
+            interest_only_terms: dailys
+                .iter()
+                .flat_map(|x| x.notable_events.iter())
+                .filter(|e| matches!(e, NotableEvents::InterestOnlyInstallment(_)))
+                .count() as i32,
+            rate_cap_triggers: rate_limit_triggers(&dailys, RateLimit::Cap),
+            rate_floor_triggers: rate_limit_triggers(&dailys, RateLimit::Floor),
+            rate_change_limit_triggers: rate_limit_triggers(&dailys, RateLimit::Change),
+        })
+    }
+
+    /// The interest saved by all extra installments, compared to the same loan without them.
+    ///
+    /// The loan is calculated once more without the extra installments, which is why
+    /// the savings are not part of [`InteractiveCalculator::compute`].
+    pub fn interest_saved_by_extra(&self) -> Result<f64, String> {
+        if !self.events().any(|(_, e)| matches!(e, LoanEvent::Extra(_))) {
+            return Ok(0.0);
+        }
+
+        self.interest_saved_compared_to(Calculation::WithoutExtra)
+    }
+
+    /// The interest saved by the limits on the nominal interest,
+    /// compared to the same loan without them.
+    /// This is negative when the limits cost more interest than they saved.
+    ///
+    /// The loan is calculated once more without the limits, which is why
+    /// the savings are not part of [`InteractiveCalculator::compute`].
+    pub fn interest_saved_by_rate_limits(&self) -> Result<f64, String> {
+        // SAFETY(unwrap): events always contains the initial event.
+        let (_, initial) = self.events().next().unwrap();
+        if initial.initial().rate_limits.is_empty() {
+            return Ok(0.0);
+        }
+
+        self.interest_saved_compared_to(Calculation::WithoutRateLimits)
+    }
+
+    /// The interest of a variant of the loan, beyond the interest of the loan itself.
+    fn interest_saved_compared_to(&self, calculation: Calculation) -> Result<f64, String> {
+        let interest = |dailys: &[Daily]| dailys.iter().map(|x| x.compounded_interest).sum::<f64>();
+        let (loan, _) = self.compute_dailys()?;
+        let (variant, _) = self.compute_calculation(calculation)?;
+        Ok(interest(&variant) - interest(&loan))
+    }
+
+    /// Quote the amount required to repay the entire loan on date,
+    /// taking every event prior to the date into account.
+    ///
+    /// The fees of the first early payoff event on or after the date apply to the quote,
+    /// otherwise the loan is quoted without any fees for closing it.
+    pub fn payoff_quote(&self, date: NaiveDate) -> Result<PayoffQuote, String> {
+        let is_payoff = |e: &LoanEvent| matches!(e, LoanEvent::EarlyPayoff(_));
+        let payoff = self
+            .events
+            .range(date..)
+            .flat_map(|(_, events)| events.iter())
+            .find_map(|e| match e {
+                LoanEvent::EarlyPayoff(payoff) => Some(payoff.clone()),
+                _ => None,
+            })
+            .unwrap_or_default();
+
+        let mut quoted = self.clone();
+        for (_, events) in quoted.events.range_mut(date..) {
+            events.retain(|e| !is_payoff(e));
+        }
+        quoted.events.retain(|_, events| !events.is_empty());
+        quoted.add_event_early_payoff(date, payoff)?;
+
+        let (dailys, _) = quoted.compute_dailys()?;
+        dailys
+            .last()
+            .and_then(|d| {
+                d.notable_events.iter().find_map(|e| match e {
+                    NotableEvents::EarlyPayoff(quote) => Some(*quote),
+                    _ => None,
+                })
+            })
+            .ok_or_else(|| format!("the loan is repayed in full by {}", date))
+    }
+
+    /// Compute the daily ledger for the lifetime of the loan based on current events.
+    ///
+    /// Only days where any action was processed on the loan are included, together with
+    /// every month end, while [`Engine::Daily`] includes every day of the loan.
+    pub fn ledger(&self) -> Result<Vec<Daily>, String> {
+        self.compute_dailys().map(|(dailys, _)| dailys)
+    }
+
+    /// The state of the loan at the end of date, after all actions on the date are processed.
+    ///
+    /// The calculation only runs up to the date, rather than the lifetime of the loan.
+    pub fn snapshot(&self, date: NaiveDate) -> Result<LoanSnapshot, String> {
+        // SAFETY(unwrap): a single date results in a single snapshot.
+        self.snapshots(&[date]).map(|mut s| s.pop().unwrap())
+    }
+
+    /// The state of the loan at the end of every date, in the order of the provided dates.
+    ///
+    /// The calculation runs once, up to the latest of the dates.
+    pub fn snapshots(&self, dates: &[NaiveDate]) -> Result<Vec<LoanSnapshot>, String> {
+        let until = match dates.iter().max() {
+            Some(until) => *until,
+            None => return Ok(Vec::new()),
+        };
+        if dates.iter().any(|date| *date < self.disbursement_date()) {
+            return Err("expecting snapshot dates on or after the disbursement date".to_string());
+        }
+
+        let mut order: Vec<usize> = (0..dates.len()).collect();
+        order.sort_by_key(|i| dates[*i]);
+        let mut pending = order.into_iter().peekable();
+        let mut snapshots = vec![None; dates.len()];
+
+        // Every date prior to the next processed day sees the current state.
+        let mut observe = |current_date: NaiveDate, state: &CurrentCalculationState| {
+            while let Some(i) = pending.next_if(|i| dates[*i] < current_date) {
+                snapshots[i] = Some(snapshot_of(dates[i], state));
+            }
+        };
+        let first = dates.iter().min().unwrap_or(&until);
+        let resume = self.cache.resume(Calculation::Loan, Some(first.succ()));
+        let (_, state) =
+            self.run_until(Calculation::Loan, resume, Some(until), &mut observe, None)?;
+
+        // The remaining dates are on or after the last processed day.
+        for i in pending {
+            snapshots[i] = Some(snapshot_of(dates[i], &state));
+        }
+
+        // SAFETY(unwrap): every date has been observed above.
+        Ok(snapshots.into_iter().map(|s| s.unwrap()).collect())
+    }
+
+    /// Run the calculation over all events, returning every processed day
+    /// together with the final calculation state.
+    fn compute_dailys(&self) -> Result<(Vec<Daily>, CurrentCalculationState), String> {
+        self.compute_calculation(Calculation::Loan)
+    }
+
+    /// Run a complete calculation, resuming from the latest checkpoint still valid
+    /// after any edits since the previous calculation.
+    fn compute_calculation(
+        &self,
+        calculation: Calculation,
+    ) -> Result<(Vec<Daily>, CurrentCalculationState), String> {
+        if let Some(complete) = self.cache.complete(calculation) {
+            return Ok(complete);
+        }
+
+        let resume = self.cache.resume(calculation, None);
+        let mut checkpoints = Vec::new();
+        let (dailys, state) = self.run_until(
+            calculation,
+            resume,
+            None,
+            &mut |_, _| {},
+            Some(&mut checkpoints),
+        )?;
+        self.cache.store(calculation, checkpoints, &dailys, &state);
+
+        Ok((dailys, state))
+    }
+
+    /// The events of a calculation, where the variants of the loan are derived from its events.
+    fn calculation_events(
+        &self,
+        calculation: Calculation,
+    ) -> Cow<'_, BTreeMap<NaiveDate, Vec<LoanEvent>>> {
+        match calculation {
+            Calculation::Loan => Cow::Borrowed(&self.events),
+            Calculation::WithoutExtra => {
+                let mut events = self.events.clone();
+                for events in events.values_mut() {
+                    events.retain(|e| !matches!(e, LoanEvent::Extra(_)));
+                }
+                events.retain(|_, events| !events.is_empty());
+                Cow::Owned(events)
+            }
+            Calculation::WithoutRateLimits => {
+                let mut events = self.events.clone();
+                // SAFETY(unwrap): events always contains the initial event.
+                if let Some(LoanEvent::Initial(initial)) =
+                    events.values_mut().next().unwrap().first_mut()
+                {
+                    initial.rate_limits = RateLimits::default();
+                }
+                Cow::Owned(events)
+            }
+        }
+    }
+
+    /// Run the calculation over all events up to and including the date until,
+    /// or the lifetime of the loan, returning every processed day together with
+    /// the calculation state.
+    ///
+    /// The calculation starts from the disbursement, unless a checkpoint to resume from
+    /// is provided. The state is observed ahead of every day, reflecting every day prior
+    /// to it. Checkpoints for later calculations are recorded, if requested.
+    fn run_until<F>(
+        &self,
+        calculation: Calculation,
+        resume: Option<(NaiveDate, Checkpoint, Vec<Daily>)>,
+        until: Option<NaiveDate>,
+        observe: &mut F,
+        mut checkpoints: Option<&mut Vec<(NaiveDate, Checkpoint)>>,
+    ) -> Result<(Vec<Daily>, CurrentCalculationState), String>
+    where
+        F: FnMut(NaiveDate, &CurrentCalculationState),
+    {
+        let events = self.calculation_events(calculation);
 
         // SAFETY(unwrap): events vector always contains 1 element.
-        let (payout_date, initial) = events_iter.next().unwrap();
+        let (payout_date, initial) = events.iter().next().unwrap();
         if initial.len() > 1 {
             return Err(
                 "Unexpected amount of events on loan initialization, expected one".to_string(),
@@ -197,39 +876,150 @@ impl InteractiveCalculator {
             );
         }
 
-        let mut state = initial_computing_state(payout_date, initial);
+        if initial.interest_only_terms >= initial.terms {
+            return Err(
+                "expecting fewer interest-only terms than terms in initial loan event".to_string(),
+            );
+        }
+
+        initial.rate_limits.validate()?;
 
-        // Calculate future actions based on initial
-        let mut daily_actions = compute_actions_on_disbursement(
-            initial.loan,
-            initial.administration_fee,
-            &payout_date,
-            &state,
+        // Events changed in place are validated here, rather than when they are added.
+        for event in events.values().flatten() {
+            if let LoanEvent::InterestChange(change) = event {
+                change.validate()?;
+            }
+        }
+
+        let mut state = initial_computing_state(
+            payout_date,
+            initial,
+            self.engine,
+            self.compounding_strategy,
+            self.holiday_calendar.clone(),
         );
+        if state.balloon < 0.0 || state.balloon >= initial.loan {
+            return Err(
+                "expecting a balloon of zero or more, and less than the loan in initial loan event"
+                    .to_string(),
+            );
+        }
+
+        // The floating rate is applied as interest changes ahead of any other events on the
+        // same date, while a floating rate from the disbursement is the initial interest.
+        let floating_events;
+        let events = match floating_rate_changes(payout_date, initial)? {
+            changes if changes.is_empty() => events.as_ref(),
+            changes => {
+                let mut events = events.as_ref().clone();
+                for (date, nominal_interest) in changes {
+                    if &date == payout_date {
+                        state.current_nominal_interest = nominal_interest;
+                        continue;
+                    }
+                    let change = LoanInterestChange { nominal_interest };
+                    events
+                        .entry(date)
+                        .or_default()
+                        .insert(0, LoanEvent::InterestChange(change));
+                }
+                floating_events = events;
+                &floating_events
+            }
+        };
+
+        // Calculate future actions based on initial, unless resuming from a checkpoint.
+        let (start_date, mut daily_actions, mut dailys) = match resume {
+            Some((date, checkpoint, dailys)) => {
+                state = checkpoint.state;
+                (date, checkpoint.actions, dailys)
+            }
+            None => {
+                let actions = compute_actions_on_disbursement(
+                    initial.loan,
+                    initial.administration_fee,
+                    payout_date,
+                    &state,
+                );
+                (*payout_date, ScheduledActions::new(actions), Vec::new())
+            }
+        };
 
         // We can now consume future events as their date approaches.
+        let mut events_iter = events.range(start_date.max(payout_date.succ())..);
         let mut potential_events = events_iter.next();
 
-        let mut dailys = Vec::new();
-        for current_date in payout_date.iter_days() {
+        let mut current_date = start_date;
+        let mut previous_date = start_date;
+        loop {
+            observe(current_date, &state);
+            if until.is_some_and(|until| current_date > until) {
+                break;
+            }
+
+            // Keep the state ahead of every event and every year, such that a later
+            // calculation may resume from the earliest changed date.
+            let is_event_date = potential_events.is_some_and(|(date, _)| date == &current_date);
+            let is_new_year = current_date.year() != previous_date.year();
+            if let Some(checkpoints) = checkpoints.as_mut() {
+                if current_date > start_date && (is_event_date || is_new_year) {
+                    let checkpoint = Checkpoint {
+                        state: state.clone(),
+                        actions: daily_actions.clone(),
+                        processed: dailys.len(),
+                    };
+                    checkpoints.push((current_date, checkpoint));
+                }
+            }
+
             // Handle events that may alter the daily_actions
+            let mut event_actions = DayActions::default();
             match potential_events {
                 Some((event_date, next_events)) if event_date == &current_date => {
                     // A day can have multiple events
+                    let mut reschedule = false;
                     for day_event in next_events.iter() {
                         match day_event {
+                            LoanEvent::Initial(_) => {
+                                return Err(
+                                    "unexpected initial loan event after disbursement".to_string()
+                                );
+                            }
+                            LoanEvent::InterestChange(change) => {
+                                event_actions.interest_change = Some(change.nominal_interest);
+                            }
+                            LoanEvent::BankTransfer(transfer) => {
+                                // Only extra installments prior to this event remain.
+                                state
+                                    .scheduled_extra_installments
+                                    .split_off(&current_date.succ());
+                                event_actions.transfer =
+                                    Some((transfer.administration_fee, transfer.prepayment_fee));
+                            }
+                            LoanEvent::Refinance(refinance) => {
+                                event_actions.refinance =
+                                    Some((refinance.loan_increase, refinance.administration_fee));
+                            }
                             LoanEvent::Extra(schedule) => {
-                                // Handle the extra payment
-                                // TODO: Support more than single day extra payment
-                                if let Some((action_date, action)) = daily_actions.get_mut(0) {
-                                    debug_assert!(action_date == event_date);
-                                    action.extra_installments.push(schedule.amount)
-                                }
+                                schedule_extra_installments(&mut state, current_date, schedule);
+                            }
+                            LoanEvent::RepaymentFreeze(freeze) => {
+                                state.pending_repayment_freezes += freeze.count.get();
+                                reschedule = true;
+                            }
+                            LoanEvent::EarlyPayoff(payoff) => {
+                                event_actions.payoff =
+                                    Some((payoff.administration_fee, payoff.prepayment_fee));
                             }
-                            _ => {}
                         }
                     }
 
+                    // The set of installments has changed, reconstruct all future actions.
+                    if reschedule {
+                        daily_actions =
+                            ScheduledActions::new(compute_future_actions(&current_date, &state));
+                    }
+
                     potential_events = events_iter.next();
                 }
                 Some((event_date, _)) if event_date < &current_date => {
@@ -240,9 +1030,13 @@ impl InteractiveCalculator {
             }
 
             // Retrieve this days actions.
-            let actions = match Self::fetch_date_action(current_date, &mut daily_actions) {
+            let mut actions = match daily_actions.fetch(current_date) {
                 Ok(Some(a)) => a,
-                Ok(None) => continue,
+                // Only events take place on this date, while the interest accrues.
+                Ok(None) => DayActions {
+                    interest_accumulating: true,
+                    ..Default::default()
+                },
                 Err(e) => {
                     // We currently panic on this action.
                     // This is ONLY due a an inconsistency in the day actions implementation.
@@ -251,70 +1045,40 @@ impl InteractiveCalculator {
                 }
             };
 
+            actions.merge_events(event_actions);
+            if let Some(extras) = state.scheduled_extra_installments.remove(&current_date) {
+                actions.extra_installments.extend(extras);
+            }
+
             // Process this days actions.
             let (daily, finished) = Self::process_day_action(&mut state, current_date, actions);
             dailys.push(daily);
 
             if finished {
+                state.finished = true;
                 break;
             }
-        }
-
-        return Ok(TotalResult {
-            total_cost: dailys.iter().map(|x| x.repayed).sum(),
-            total_loan: dailys.iter().map(|x| x.disbursed).sum(),
-            total_repayment_installment: dailys.iter().map(|x| x.repayment_installment).sum(),
-            total_extra_installment: dailys.iter().map(|x| x.extra_installment).sum(),
-            total_interest: dailys.iter().map(|x| x.compounded_interest).sum(),
-            total_fee: dailys.iter().map(|x| x.fee).sum(),
 
-            disbursement_date: dailys.first().unwrap().date.clone(),
-            first_installment_date: dailys
+            previous_date = current_date;
+            current_date = match state.engine {
+                Engine::Daily => current_date.succ(),
+                // Jump to the next date with any scheduled action, event or extra installment.
+                Engine::EventDriven => [
+                    daily_actions.next_date(),
+                    potential_events.map(|(date, _)| *date),
+                    state.scheduled_extra_installments.keys().next().copied(),
+                ]
                 .iter()
-                .find(|x| x.repayment_installment > 0.0)
-                .map_or(NaiveDate::from_ymd(1970, 1, 1), |x| x.date.clone()),
-            end_date: dailys.last().unwrap().date.clone(),
-            planned_terms: state.planned_repayment_terms as i32,
-            completed_terms: state.completed_repayment_terms as i32,
-        });
-    }
-
-    /// Returns Ok(Some(...)) if actions array has actions for this date.
-    /// Returns Ok(None) if there are no actions for this date
-    /// Return Err(...) on fatal error (logic break)
-    fn fetch_date_action(
-        date: NaiveDate,
-        actions: &mut VecDeque<(NaiveDate, DayActions)>,
-    ) -> Result<Option<DayActions>, String> {
-        // TODO: Improve this data structure - we can do better
-        // Fetch this days actions
-        match actions.front() {
-            Some((action_date, _)) => {
-                if action_date > &date {
-                    // Date in the future. Continue with none
-                    return Ok(None);
-                }
-            }
-            None => {
-                // The actions set is empty
-                // This is effectively a condition where we cannot terminate
-                return Err(
-                    "no more daily actions, yet we have not terminated computation".to_string(),
-                );
-            }
+                .flatten()
+                .min()
+                .copied()
+                .ok_or_else(|| {
+                    "no more daily actions, yet we have not terminated computation".to_string()
+                })?,
+            };
         }
 
-        if let Some((action_date, a)) = actions.pop_front() {
-            if action_date < date {
-                return Err(
-                    "action events where in the past - this means we skipped some events"
-                        .to_string(),
-                );
-            }
-            return Ok(Some(a));
-        };
-
-        unreachable!("pop_front should be guarded");
+        Ok((dailys, state))
     }
 
     /// Process a single day of actions.
@@ -330,58 +1094,148 @@ impl InteractiveCalculator {
         let mut daily_repayed = 0.0;
         let mut daily_interest_installment = 0.0;
         let mut daily_repayment_installment = 0.0;
+        let mut daily_installment_fee = 0.0;
+        let mut daily_prepayment_fee = 0.0;
         let mut daily_extra_installment = 0.0;
+        let mut daily_balloon_installment = 0.0;
+        let mut daily_payoff_installment = 0.0;
+        let mut daily_payoff_fee = 0.0;
         let mut daily_compounded_interest = 0.0;
         let mut daily_fees = 0.0;
         let mut daily_disbursed = 0.0;
 
         let mut notable = Vec::new();
 
+        // Accrue the interest of the quiet days since the last processed day in closed form,
+        // as neither the outstanding loan nor the nominal interest changed in between.
+        let quiet_days = (date - state.interest_accrued_until).num_days() - 1;
+        if actions.interest_accumulating && quiet_days > 0 {
+            let quiet_interest = daily_interest(state) * quiet_days as f64;
+            daily_accrued_interest += quiet_interest;
+            state.accrued_interest += quiet_interest;
+            state.accrued_interest_since_last_installment += quiet_interest;
+        }
+        state.interest_accrued_until = date;
+
         if let Some((amount, fee)) = actions.initialization {
             state.current_outstanding_loan += amount + fee;
             state.original_outstanding_loan = state.current_outstanding_loan;
             daily_fees += fee;
             daily_disbursed += amount;
 
-            state.computed_effective_interest = effective_interest(
-                state.current_nominal_interest,
-                state.current_terms_per_year.to_u32(),
-            );
-            state.computed_term_payment = annuity_term_payment(
-                state.original_outstanding_loan,
-                state.computed_effective_interest,
-                state.current_terms_per_year.to_u32(),
-                state.planned_repayment_terms - state.completed_repayment_terms,
-            );
+            // The initial nominal interest is held within the cap and the floor.
+            let requested = state.current_nominal_interest;
+            let (nominal_interest, limit) = state.rate_limits.apply(None, requested);
+            state.current_nominal_interest = nominal_interest;
+            if let Some(limit) = limit {
+                notable.push(NotableEvents::RateLimited(limit, requested));
+            }
+
+            recompute_term_payment(state);
             notable.push(NotableEvents::Initialization(amount));
         }
 
+        if let Some(requested) = actions.interest_change {
+            let (nominal_interest, limit) = state
+                .rate_limits
+                .apply(Some(state.current_nominal_interest), requested);
+            state.current_nominal_interest = nominal_interest;
+            recompute_term_payment(state);
+            notable.push(NotableEvents::InterestChange(nominal_interest));
+            if let Some(limit) = limit {
+                notable.push(NotableEvents::RateLimited(limit, requested));
+            }
+        }
+
+        // Mark the first day of the floating rate, where the term payment may change.
+        if state.fixed_until.map(|d| d.succ()) == Some(date) {
+            notable.push(NotableEvents::FixedRateEnd(state.current_nominal_interest));
+        }
+
+        if let Some((amount, fee)) = actions.refinance {
+            state.current_outstanding_loan += amount + fee;
+            daily_fees += fee;
+            daily_disbursed += amount;
+
+            recompute_term_payment(state);
+            notable.push(NotableEvents::Refinance(amount));
+        }
+
+        // Settle the interest accrued with the previous bank, before the new
+        // bank establishes its calculations on the outstanding loan.
+        if let Some((fee, prepayment_fee)) = actions.transfer {
+            daily_compounded_interest += state.accrued_interest;
+            state.current_outstanding_loan += state.accrued_interest;
+            state.accrued_interest = 0.0;
+
+            daily_interest_installment += state.accrued_interest_since_last_installment;
+            daily_repayed += state.accrued_interest_since_last_installment;
+            state.current_outstanding_loan -= state.accrued_interest_since_last_installment;
+            state.accrued_interest_since_last_installment = 0.0;
+
+            // The previous bank charges for the early repayment of the entire loan.
+            if let Some(prepayment_fee) = prepayment_fee {
+                let fee = prepayment_fee.fee_of(
+                    state.current_outstanding_loan,
+                    date,
+                    state.current_nominal_interest,
+                );
+                daily_prepayment_fee += fee;
+                notable.push(NotableEvents::PrepaymentFee(fee));
+            }
+
+            state.current_outstanding_loan += fee;
+            daily_fees += fee;
+
+            recompute_term_payment(state);
+            notable.push(NotableEvents::BankTransfer(state.current_outstanding_loan));
+        }
+
         // Accumulate interest on outstanding principal loan.
         if actions.interest_accumulating {
             // We currently implement interest accumulation by daily increment.
-            daily_accrued_interest = (state.current_outstanding_loan
-                * (state.current_nominal_interest / 100f64))
-                / 365f64;
-            state.accrued_interest += daily_accrued_interest;
-            state.accrued_interest_since_last_installment += daily_accrued_interest;
+            let interest = daily_interest(state);
+            daily_accrued_interest += interest;
+            state.accrued_interest += interest;
+            state.accrued_interest_since_last_installment += interest;
         }
 
         // If any extra installments have been scheduled on this day, we need to account
-        // for it.
-        //
-        // TODO: Check if the extra installment would brind the outstanding loan to zero.
-        // Could be useful to do this check after installment check as well.
-        for extra in actions.extra_installments.iter() {
+        // for it. An extra installment can at most repay the outstanding loan,
+        // any non-posted interest is settled by the next scheduled installment.
+        let mut lower_payment = false;
+        for scheduled in actions.extra_installments.iter() {
+            let extra = scheduled
+                .amount
+                .min(state.current_outstanding_loan.max(0.0));
             daily_extra_installment += extra;
             daily_repayed += extra;
             state.current_outstanding_loan -= extra;
 
-            notable.push(NotableEvents::ExtraInstallment(*extra));
+            lower_payment |=
+                extra > 0.0 && scheduled.policy == ExtraInstallmentPolicy::LowerPayment;
+            notable.push(NotableEvents::ExtraInstallment(extra));
+
+            if let Some(prepayment_fee) = scheduled.prepayment_fee.filter(|_| extra > 0.0) {
+                let fee = prepayment_fee.fee_of(extra, date, state.current_nominal_interest);
+                daily_prepayment_fee += fee;
+                notable.push(NotableEvents::PrepaymentFee(fee));
+            }
+        }
+
+        // Prepayment fees are payed on the date of the prepayment, outside the loan.
+        daily_fees += daily_prepayment_fee;
+        daily_repayed += daily_prepayment_fee;
+
+        // Spread the lowered outstanding loan over the remaining terms, rather than
+        // keeping the term payment and finishing the loan early.
+        if lower_payment {
+            recompute_term_payment(state);
         }
 
         // Check if we should post the accrued interest to the loan
         if actions.interest_compounding {
-            daily_compounded_interest = state.accrued_interest;
+            daily_compounded_interest += state.accrued_interest;
             state.accrued_interest = 0.0;
             state.current_outstanding_loan += daily_compounded_interest;
         }
@@ -389,16 +1243,26 @@ impl InteractiveCalculator {
         // Installment on repayment - this includes repayment of an interest portion.
         match actions.installment {
             Some(InstallmentType::Repayment) => {
-                // TODO(serial loans): Once we support serial loans, this term payment
-                // must include the computed accrued interest
-                let term_payment = state.computed_term_payment;
+                // A serial term repays a fixed portion of the loan, together with the
+                // interest accrued since the last installment.
+                let term_payment = match state.amortisation {
+                    Amortisation::Annuity => state.computed_term_payment,
+                    Amortisation::Serial => {
+                        state.computed_term_payment + state.accrued_interest_since_last_installment
+                    }
+                };
 
                 // Check if the current outstanding loan, including non-posted interest,
                 // could be fulfilled by a complete term payment.
+                // The last planned term settles the remaining loan, including any balloon.
                 let total = state.current_outstanding_loan
                     + state.current_installment_fee
                     + state.accrued_interest;
-                let payment = if term_payment > total {
+                let last_term =
+                    state.completed_repayment_terms + 1 >= state.planned_repayment_terms;
+                let payment = if last_term
+                    || term_payment + state.current_installment_fee + ROUNDING_RESIDUE >= total
+                {
                     finished = true;
                     total
                 } else {
@@ -406,7 +1270,9 @@ impl InteractiveCalculator {
                 };
 
                 // Update some daily metrics.
-                daily_interest_installment = state.accrued_interest_since_last_installment;
+                let interest_portion = state.accrued_interest_since_last_installment;
+                daily_interest_installment += interest_portion;
+                daily_installment_fee = state.current_installment_fee;
                 daily_fees += state.current_installment_fee;
                 daily_repayed += payment;
 
@@ -425,11 +1291,18 @@ impl InteractiveCalculator {
                     state.accrued_interest = 0.0;
                 }
 
-                daily_repayment_installment = payment - daily_interest_installment;
+                daily_repayment_installment = payment - interest_portion - daily_installment_fee;
                 state.current_outstanding_loan -= payment;
                 state.completed_repayment_terms += 1;
                 notable.push(NotableEvents::RepaymentInstallment(payment));
 
+                // The part of the final payment beyond the regular term payment is the balloon.
+                if finished && state.balloon > 0.0 {
+                    daily_balloon_installment =
+                        (payment - term_payment - state.current_installment_fee).max(0.0);
+                    notable.push(NotableEvents::BalloonInstallment(daily_balloon_installment));
+                }
+
                 // TODO: We have some corner cases where our calculations are off
                 // with very low numbers. This has manifested itself as negative
                 // current_outstanding_loan. We workaround this issue now
@@ -441,17 +1314,81 @@ impl InteractiveCalculator {
             }
             Some(InstallmentType::InterestOnly) => {
                 // We only process a interest installment if we have not processed a repayment.
-                daily_interest_installment =
-                    state.accrued_interest_since_last_installment + daily_accrued_interest;
+                // All interest accrued until today is posted and payed in full.
+                daily_compounded_interest += state.accrued_interest;
+                state.current_outstanding_loan += state.accrued_interest;
+                state.accrued_interest = 0.0;
+
+                daily_interest_installment += state.accrued_interest_since_last_installment;
+                daily_repayed += state.accrued_interest_since_last_installment;
+                state.current_outstanding_loan -= state.accrued_interest_since_last_installment;
                 state.accrued_interest_since_last_installment = 0.0;
-                daily_repayed += daily_interest_installment;
+
+                // The initial interest-only terms come first, and include the installment fee.
+                if state.pending_interest_only_terms > 0 {
+                    state.pending_interest_only_terms -= 1;
+                    daily_installment_fee = state.current_installment_fee;
+                    daily_fees += state.current_installment_fee;
+                    daily_repayed += state.current_installment_fee;
+                } else {
+                    state.pending_repayment_freezes =
+                        state.pending_repayment_freezes.saturating_sub(1);
+                }
                 notable.push(NotableEvents::InterestOnlyInstallment(
-                    daily_interest_installment,
+                    daily_interest_installment + daily_installment_fee,
                 ));
             }
             None => {}
         }
 
+        // Repay the entire loan after any installment on this day, settling the interest
+        // accrued since the last installment. The fees are payed outside the loan.
+        if let Some((fee, prepayment_fee)) = actions.payoff.filter(|_| !finished) {
+            daily_compounded_interest += state.accrued_interest;
+            state.current_outstanding_loan += state.accrued_interest;
+            state.accrued_interest = 0.0;
+
+            let interest = state.accrued_interest_since_last_installment;
+            state.current_outstanding_loan -= interest;
+            state.accrued_interest_since_last_installment = 0.0;
+
+            let principal = state.current_outstanding_loan.max(0.0);
+            let prepayment_fee = prepayment_fee.map_or(0.0, |prepayment_fee| {
+                prepayment_fee.fee_of(principal, date, state.current_nominal_interest)
+            });
+            let quote = PayoffQuote {
+                date,
+                outstanding_principal: principal,
+                accrued_interest: interest,
+                administration_fee: fee,
+                prepayment_fee,
+                total: principal + interest + fee + prepayment_fee,
+            };
+
+            daily_interest_installment += interest;
+            daily_payoff_installment = principal;
+            daily_payoff_fee = fee;
+            daily_prepayment_fee += prepayment_fee;
+            daily_fees += fee + prepayment_fee;
+            daily_repayed += quote.total;
+            state.current_outstanding_loan -= principal;
+
+            if prepayment_fee > 0.0 {
+                notable.push(NotableEvents::PrepaymentFee(prepayment_fee));
+            }
+            notable.push(NotableEvents::EarlyPayoff(quote));
+            finished = true;
+        }
+
+        // Keep track of the next installment, such that future actions may be reconstructed.
+        if actions.installment.is_some() {
+            state.computed_installment_date = installment_date_from_interval(
+                &state.computed_installment_date,
+                state.current_monthly_due_day,
+                state.current_terms_per_year,
+            );
+        }
+
         let daily = Daily {
             date,
             accrued_interest: daily_accrued_interest,
@@ -461,7 +1398,13 @@ impl InteractiveCalculator {
             repayed: daily_repayed,
             repayment_installment: daily_repayment_installment,
             interest_installment: daily_interest_installment,
+            installment_fee: daily_installment_fee,
+            prepayment_fee: daily_prepayment_fee,
             extra_installment: daily_extra_installment,
+            balloon_installment: daily_balloon_installment,
+            payoff_installment: daily_payoff_installment,
+            payoff_fee: daily_payoff_fee,
+            outstanding_loan: state.current_outstanding_loan,
 
             notable_events: notable,
         };
@@ -470,20 +1413,83 @@ impl InteractiveCalculator {
     }
 }
 
+/// The interest accrued on the outstanding loan in a single day.
+fn daily_interest(state: &CurrentCalculationState) -> f64 {
+    (state.current_outstanding_loan * (state.current_nominal_interest / 100f64)) / 365f64
+}
+
+/// The public view of the calculation state, as of the end of date.
+fn snapshot_of(date: NaiveDate, state: &CurrentCalculationState) -> LoanSnapshot {
+    // Any quiet days since the last processed day accrue interest in closed form.
+    let quiet_days = (date - state.interest_accrued_until).num_days();
+    let quiet_interest = if state.finished || quiet_days <= 0 {
+        0.0
+    } else {
+        daily_interest(state) * quiet_days as f64
+    };
+
+    LoanSnapshot {
+        date,
+        outstanding_loan: state.current_outstanding_loan.max(0.0),
+        accrued_interest: state.accrued_interest + quiet_interest,
+        interest_since_last_installment: state.accrued_interest_since_last_installment
+            + quiet_interest,
+        nominal_interest: state.current_nominal_interest,
+        term_payment: state.computed_term_payment,
+        installment_fee: state.current_installment_fee,
+        terms_per_year: state.current_terms_per_year,
+        planned_terms: state.planned_repayment_terms,
+        completed_terms: state.completed_repayment_terms,
+        pending_interest_only_terms: state.pending_interest_only_terms
+            + state.pending_repayment_freezes,
+        next_installment_date: if state.finished {
+            None
+        } else {
+            Some(state.business_day_convention.adjust(
+                state.computed_installment_date,
+                state.holiday_calendar.as_ref(),
+            ))
+        },
+        finished: state.finished,
+    }
+}
+
 fn initial_computing_state(
     payout_date: &NaiveDate,
     initial: &LoanInitialization,
+    engine: Engine,
+    interest_compounding_strategy: CompoundingStrategy,
+    holiday_calendar: Arc<dyn HolidayCalendar + Send + Sync>,
 ) -> CurrentCalculationState {
-    let computed_installment_date = installment_date_from_target_month(
+    let mut computed_installment_date = installment_date_from_target_month(
         payout_date,
         initial.due_within_month,
         initial.first_installment_month,
     );
+    // Day-based terms are due on a fixed weekday.
+    if let (Some(_), Some(weekday)) = (
+        initial.terms_per_year.days_between_terms(),
+        initial.due_weekday,
+    ) {
+        while computed_installment_date.weekday() != weekday {
+            computed_installment_date = computed_installment_date.succ();
+        }
+    }
 
     CurrentCalculationState {
-        interest_compounding_strategy: CompoundingStrategy::OnInstallment,
-        planned_repayment_terms: initial.terms,
+        interest_compounding_strategy,
+        engine,
+        interest_accrued_until: *payout_date,
+        planned_repayment_terms: initial.terms - initial.interest_only_terms,
         completed_repayment_terms: 0,
+        pending_repayment_freezes: 0,
+        pending_interest_only_terms: initial.interest_only_terms,
+        scheduled_extra_installments: BTreeMap::new(),
+        amortisation: initial.amortisation,
+        extra_policy: initial.extra_policy,
+        balloon: initial
+            .balloon
+            .map_or(0.0, |balloon| balloon.amount_of(initial.loan)),
         original_outstanding_loan: 0.0,
 
         accrued_interest: 0.0,
@@ -495,9 +1501,17 @@ fn initial_computing_state(
         current_terms_per_year: initial.terms_per_year,
         current_monthly_due_day: initial.due_within_month,
 
+        business_day_convention: initial.business_day_convention,
+        holiday_calendar,
+
+        fixed_until: initial.fixed_until,
+        rate_limits: initial.rate_limits,
+
         computed_installment_date,
         computed_effective_interest: 0.0,
         computed_term_payment: 0.0,
+
+        finished: false,
     }
 }
 
@@ -511,39 +1525,48 @@ fn compute_actions_on_disbursement(
     current_date: &NaiveDate,
     state: &CurrentCalculationState,
 ) -> VecDeque<(NaiveDate, DayActions)> {
-    // Set of return actions
-    let mut all_actions = {
-        let a = DayActions {
-            initialization: Some((amount, fee)),
-            ..Default::default()
-        };
-        let mut v = VecDeque::new();
-        v.push_front((current_date.clone(), a));
-        v
+    let a = DayActions {
+        initialization: Some((amount, fee)),
+        ..Default::default()
     };
 
-    let mut completed_repayments = 0;
-    let mut skip_installments = 0;
-    let mut next_installment_date = state.computed_installment_date;
+    let mut all_actions = compute_future_actions(&current_date.succ(), state);
+    all_actions.push_front((*current_date, a));
+    all_actions
+}
 
-    for date in current_date.succ().iter_days() {
-        if completed_repayments == state.planned_repayment_terms {
-            break;
-        }
+/// Compute the set of DayActions from, and including, the provided date
+/// for the remainder of this loan, based on the current calculation state.
+fn compute_future_actions(
+    from_date: &NaiveDate,
+    state: &CurrentCalculationState,
+) -> VecDeque<(NaiveDate, DayActions)> {
+    let mut all_actions = VecDeque::new();
 
+    let mut completed_repayments = state.completed_repayment_terms;
+    let mut skip_installments = state.pending_interest_only_terms + state.pending_repayment_freezes;
+    let mut next_installment_date = state.computed_installment_date;
+    // The installment is payed on a business day, but never before the first date.
+    let due_date = |installment_date: NaiveDate| {
+        state
+            .business_day_convention
+            .adjust(installment_date, state.holiday_calendar.as_ref())
+            .max(*from_date)
+    };
+    let mut next_due_date = due_date(next_installment_date);
+
+    let mut date = *from_date;
+    while completed_repayments < state.planned_repayment_terms {
         let mut actions = DayActions {
-            initialization: None,
             interest_accumulating: true,
-            interest_compounding: false,
-            installment: None,
-            extra_installments: Vec::new(),
+            ..Default::default()
         };
 
         // Check for if we have any installment type for today
-        actions.installment = if next_installment_date == date && skip_installments > 0 {
+        actions.installment = if next_due_date == date && skip_installments > 0 {
             skip_installments -= 1;
             Some(InstallmentType::InterestOnly)
-        } else if next_installment_date == date {
+        } else if next_due_date == date {
             completed_repayments += 1;
             Some(InstallmentType::Repayment)
         } else {
@@ -551,51 +1574,58 @@ fn compute_actions_on_disbursement(
         };
 
         actions.interest_compounding = match state.interest_compounding_strategy {
-            CompoundingStrategy::EndOfYear => {
-                if date.succ().year() != date.year() {
-                    true
-                } else {
-                    false
-                }
-            }
-            CompoundingStrategy::EndOfMonth => {
-                if date.succ().month() != date.month() {
-                    true
-                } else {
-                    false
-                }
-            }
-            CompoundingStrategy::OnInstallment => {
-                if date == next_installment_date {
-                    true
-                } else {
-                    false
-                }
-            }
+            CompoundingStrategy::EndOfYear => date.succ().year() != date.year(),
+            CompoundingStrategy::EndOfMonth => date.succ().month() != date.month(),
+            CompoundingStrategy::OnInstallment => date == next_due_date,
             CompoundingStrategy::Daily => true,
         };
 
         // Update next_installment_date, if needed
-        if date == next_installment_date {
+        if date == next_due_date {
             next_installment_date = installment_date_from_interval(
-                &date,
+                &next_installment_date,
                 state.current_monthly_due_day,
                 state.current_terms_per_year,
             );
+            next_due_date = due_date(next_installment_date);
         }
 
-        all_actions.push_back((date, actions));
+        // The event-driven engine only processes the significant dates, where the interest
+        // of the quiet days in between is accrued in closed form. Month ends are kept,
+        // such that the accrued interest is reported within the month and year it belongs to.
+        let month_end = date.succ().month() != date.month();
+        let significant = actions.installment.is_some() || actions.interest_compounding;
+        if state.engine == Engine::Daily || significant || month_end {
+            all_actions.push_back((date, actions));
+        }
+
+        date = match (state.engine, state.interest_compounding_strategy) {
+            (Engine::Daily, _) | (_, CompoundingStrategy::Daily) => date.succ(),
+            (Engine::EventDriven, _) => next_due_date
+                .min(end_of_month(date.succ()))
+                .max(date.succ()),
+        };
     }
 
     all_actions
 }
 
+/// The last day of the month of date.
+fn end_of_month(date: NaiveDate) -> NaiveDate {
+    let (year, month) = match date.month() {
+        12 => (date.year() + 1, 1),
+        month => (date.year(), month + 1),
+    };
+    NaiveDate::from_ymd(year, month, 1).pred()
+}
+
 /// Calculate the next installment date based on:
 /// - current date
 /// - desired due day of month
 /// - term interval
 ///
 /// The calculation will start with the term interval offset from current date.
+/// Day-based terms are a fixed number of days apart, keeping the weekday of the current date.
 fn installment_date_from_interval(
     current: &NaiveDate,
     due: MonthlyDueDate,
@@ -608,9 +1638,14 @@ fn installment_date_from_interval(
         TermsPerYear::Four => 3,
         TermsPerYear::Six => 2,
         TermsPerYear::Twelve => 1,
+        TermsPerYear::TwentySix | TermsPerYear::FiftyTwo => {
+            // SAFETY(unwrap): day-based terms always have a number of days between them.
+            let days = interval.days_between_terms().unwrap();
+            return *current + Duration::days(days as i64);
+        }
     };
     let month = future_month(current, increase);
-    return installment_date_from_target_month(current, due, month);
+    installment_date_from_target_month(current, due, month)
 }
 
 /// Calculate the future month based on num month increments from provided date.
@@ -652,6 +1687,212 @@ fn installment_date_from_target_month(
     }
 }
 
+/// Recompute the term payment over the remaining terms of the loan,
+/// based on the current outstanding loan and nominal interest.
+fn recompute_term_payment(state: &mut CurrentCalculationState) {
+    state.computed_effective_interest = match state.current_terms_per_year.days_between_terms() {
+        Some(days) => effective_interest_by_days(state.current_nominal_interest, days),
+        None => effective_interest(
+            state.current_nominal_interest,
+            state.current_terms_per_year.to_u32(),
+        ),
+    };
+
+    let remaining_terms = state
+        .planned_repayment_terms
+        .saturating_sub(state.completed_repayment_terms);
+    // The balloon can at most be the outstanding loan, leaving only interest to pay.
+    let balloon = state.balloon.min(state.current_outstanding_loan);
+    state.computed_term_payment = if remaining_terms == 0 {
+        state.current_outstanding_loan
+    } else {
+        match state.amortisation {
+            Amortisation::Annuity => annuity_term_payment(
+                state.current_outstanding_loan,
+                term_interest(
+                    state.computed_effective_interest,
+                    state.current_terms_per_year,
+                ),
+                remaining_terms,
+                balloon,
+            ),
+            Amortisation::Serial => {
+                (state.current_outstanding_loan - balloon) / remaining_terms as f64
+            }
+        }
+    };
+}
+
+/// Compute the interest changes of the floating rate of the loan, ordered by date.
+///
+/// Rates effective before the floating rate applies are moved to the first floating date,
+/// where the latest of them is the rate the loan reverts to.
+fn floating_rate_changes(
+    payout_date: &NaiveDate,
+    initial: &LoanInitialization,
+) -> Result<Vec<(NaiveDate, f64)>, String> {
+    let floating = match (&initial.floating_rate, initial.fixed_until) {
+        (Some(floating), _) => floating,
+        (None, None) => return Ok(Vec::new()),
+        (None, Some(_)) => {
+            return Err(
+                "expecting a floating rate after the fixed-rate period in initial loan event"
+                    .to_string(),
+            )
+        }
+    };
+
+    let first_floating_date = match initial.fixed_until {
+        Some(until) if &until < payout_date => {
+            return Err(
+                "expecting the fixed-rate period to end after the disbursement date".to_string(),
+            )
+        }
+        Some(until) => until.succ(),
+        None => *payout_date,
+    };
+
+    let rates = match floating {
+        FloatingRate::Path(path) => {
+            let mut path = path.clone();
+            path.sort_by_key(|(date, _)| *date);
+            path
+        }
+        FloatingRate::Indexed(indexed) => {
+            indexed_rate_resets(first_floating_date, indexed, initial)?
+        }
+    };
+
+    let mut changes: Vec<(NaiveDate, f64)> = Vec::new();
+    for (date, nominal_interest) in rates {
+        if nominal_interest <= 0.0 {
+            return Err("expecting non-zero positive floating rates".to_string());
+        }
+
+        let date = date.max(first_floating_date);
+        match changes.last_mut() {
+            Some(last) if last.0 == date => last.1 = nominal_interest,
+            _ => changes.push((date, nominal_interest)),
+        }
+    }
+
+    Ok(changes)
+}
+
+/// Compute the nominal interest on every reset of an indexed rate, from the first floating date.
+///
+/// Resets continue until the last rate of the index has been observed, after which the
+/// nominal interest no longer changes. Only the resets that change the nominal interest are
+/// included, unless the loan limits the change on each reset. Every reset is then included,
+/// continuing until the nominal interest has caught up with the index.
+fn indexed_rate_resets(
+    first_floating_date: NaiveDate,
+    indexed: &IndexedRate,
+    initial: &LoanInitialization,
+) -> Result<Vec<(NaiveDate, f64)>, String> {
+    let last_index_date = match indexed.index.rates().last() {
+        Some((date, _)) => *date,
+        None => return Err("expecting an index with at least one rate".to_string()),
+    };
+    let lookback = Duration::days(indexed.lookback_days as i64);
+
+    // The number of resets needed for a change limit to cover the entire range of rates.
+    let catch_up = initial.rate_limits.max_change.map(|max_change| {
+        let rates = indexed
+            .index
+            .rates()
+            .iter()
+            .map(|(_, r)| r + indexed.margin);
+        let (low, high) = rates.fold(
+            (initial.nominal_interest, initial.nominal_interest),
+            |(low, high), rate| (low.min(rate), high.max(rate)),
+        );
+        ((high - low) / max_change).ceil() as u32
+    });
+
+    let mut resets = Vec::new();
+    let mut remaining_catch_up = catch_up;
+    for occurrence in 0.. {
+        let reset = recurring_date(&first_floating_date, &indexed.reset, occurrence);
+        let observed = reset - lookback;
+        let rate = indexed
+            .index
+            .rate_on(observed)
+            .ok_or_else(|| format!("expecting an index rate on or before {}", observed))?;
+        let nominal_interest = rate + indexed.margin;
+        if catch_up.is_some() || resets.last().map(|(_, last)| *last) != Some(nominal_interest) {
+            resets.push((reset, nominal_interest));
+        }
+
+        if observed >= last_index_date {
+            match remaining_catch_up {
+                Some(remaining) if remaining > 0 => remaining_catch_up = Some(remaining - 1),
+                _ => break,
+            }
+        }
+    }
+
+    Ok(resets)
+}
+
+/// The number of interest changes restricted by the provided limit.
+fn rate_limit_triggers(dailys: &[Daily], limit: RateLimit) -> i32 {
+    dailys
+        .iter()
+        .flat_map(|x| x.notable_events.iter())
+        .filter(|e| matches!(e, NotableEvents::RateLimited(l, _) if *l == limit))
+        .count() as i32
+}
+
+/// Schedule all extra installments of a recurring extra event, starting on the provided date.
+fn schedule_extra_installments(
+    state: &mut CurrentCalculationState,
+    date: NaiveDate,
+    extra: &LoanRecurringExtraInstallments,
+) {
+    let scheduled = ScheduledExtra {
+        amount: extra.amount,
+        policy: extra.policy.unwrap_or(state.extra_policy),
+        prepayment_fee: extra.prepayment_fee,
+    };
+    for occurrence in 0..extra.count.get() {
+        let extra_date = recurring_date(&date, &extra.recurring_interval, occurrence);
+        state
+            .scheduled_extra_installments
+            .entry(extra_date)
+            .or_default()
+            .push(scheduled);
+    }
+}
+
+/// Calculate the date of a recurring occurrence, counting from the start date.
+/// Monthly based intervals keep the day of month of the start date, where possible.
+fn recurring_date(start: &NaiveDate, interval: &RecurringInterval, occurrence: u32) -> NaiveDate {
+    let months = match interval {
+        RecurringInterval::Weekly => return *start + Duration::weeks(occurrence as i64),
+        RecurringInterval::Biweekly => return *start + Duration::weeks(2 * occurrence as i64),
+        RecurringInterval::Monthly => 1,
+        RecurringInterval::Bimonthly => 2,
+        RecurringInterval::Quarerly => 3,
+        RecurringInterval::Triannually => 4,
+        RecurringInterval::Biannually => 6,
+        RecurringInterval::Anually => 12,
+    };
+
+    let month0 = start.month0() + months * occurrence;
+    let year = start.year() + (month0 / 12) as i32;
+    let month = month0 % 12 + 1;
+
+    // Attempt to fully reconstruct a valid date.
+    let mut day = start.day();
+    loop {
+        if let Some(valid) = NaiveDate::from_ymd_opt(year, month, day) {
+            break valid;
+        }
+        day -= 1;
+    }
+}
+
 fn effective_interest(nominal_interest: f64, compounding_terms: u32) -> f64 {
     let effective = 1.0 + ((nominal_interest / 100.0) / compounding_terms as f64);
     let effective = f64::powi(effective, compounding_terms as i32);
@@ -659,26 +1900,43 @@ fn effective_interest(nominal_interest: f64, compounding_terms: u32) -> f64 {
     (effective - 1.0) * 100.0
 }
 
-fn annuity_term_payment(
-    principal: f64,
-    effective_interest: f64,
-    terms_per_year: u32,
-    total_terms: u32,
-) -> f64 {
+/// The effective annual interest, in percent, of a nominal interest accrued daily and
+/// compounded every provided number of days.
+fn effective_interest_by_days(nominal_interest: f64, days: u32) -> f64 {
+    let term = 1.0 + (nominal_interest / 100.0) * days as f64 / 365.0;
+    let effective = f64::powf(term, 365.0 / days as f64);
+
+    (effective - 1.0) * 100.0
+}
+
+/// The interest rate of a single term, as a fraction, from the effective annual interest.
+///
+/// Day-based terms are the exact fraction of the year they span,
+/// while other terms are an even share of the year.
+fn term_interest(effective_interest: f64, terms_per_year: TermsPerYear) -> f64 {
+    match terms_per_year.days_between_terms() {
+        Some(days) => f64::powf(1.0 + effective_interest / 100.0, days as f64 / 365.0) - 1.0,
+        None => (effective_interest / 100f64) / terms_per_year.to_u32() as f64,
+    }
+}
+
+fn annuity_term_payment(principal: f64, term_interest: f64, total_terms: u32, balloon: f64) -> f64 {
     // C = principal loan
-    // r = effective interest rate
-    // n = number of installments per year
+    // B = balloon, due with the last installment
+    // i = interest rate of a single term
     // N = total number of installments
     //
-    // top = C * (r/n)
-    // bottom = 1 - (1 + (r/n))^N
+    // top = (C - B * (1 + i)^-N) * i
+    // bottom = 1 - (1 + i)^-N
     // installment = top / bottom
 
-    let top = principal * ((effective_interest / 100f64) / terms_per_year as f64);
-    let power_result = f64::powi(
-        1f64 + ((effective_interest / 100f64) / terms_per_year as f64),
-        -(total_terms as i32),
-    );
+    // Without interest, the principal is simply spread evenly across the installments.
+    if term_interest == 0.0 {
+        return (principal - balloon) / total_terms as f64;
+    }
+
+    let power_result = f64::powi(1f64 + term_interest, -(total_terms as i32));
+    let top = (principal - balloon * power_result) * term_interest;
     let bottom = 1f64 - power_result;
 
     top / bottom
@@ -686,7 +1944,7 @@ fn annuity_term_payment(
 
 #[cfg(test)]
 mod tests {
-    use super::{installment_date_from_target_month, MonthlyDueDate};
+    use super::{annuity_term_payment, installment_date_from_target_month, MonthlyDueDate};
     use chrono::{Month, NaiveDate};
 
     #[test]
@@ -698,4 +1956,10 @@ mod tests {
         let result = installment_date_from_target_month(&today, monthly_due_day, target_month);
         assert_eq!(NaiveDate::from_ymd(2021, 2, 1), result);
     }
+
+    #[test]
+    fn annuity_term_payment_without_interest() {
+        assert_eq!(annuity_term_payment(12000.0, 0.0, 12, 0.0), 1000.0);
+        assert_eq!(annuity_term_payment(12000.0, 0.0, 12, 2400.0), 800.0);
+    }
 }
@@ -0,0 +1,151 @@
+//! Business days and holiday calendars, used to move due dates off weekends and holidays.
+
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// A calendar of the days banks are closed, beyond weekends.
+pub trait HolidayCalendar: fmt::Debug {
+    /// Whether the date is a holiday.
+    fn is_holiday(&self, date: NaiveDate) -> bool;
+
+    /// Whether the date is a business day, neither a weekend nor a holiday.
+    fn is_business_day(&self, date: NaiveDate) -> bool {
+        !matches!(date.weekday(), Weekday::Sat | Weekday::Sun) && !self.is_holiday(date)
+    }
+}
+
+/// A calendar without holidays, where only weekends are closed.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WeekendCalendar;
+
+impl HolidayCalendar for WeekendCalendar {
+    fn is_holiday(&self, _date: NaiveDate) -> bool {
+        false
+    }
+}
+
+/// The Norwegian public holidays, including those moving with Easter.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NorwegianCalendar;
+
+impl HolidayCalendar for NorwegianCalendar {
+    fn is_holiday(&self, date: NaiveDate) -> bool {
+        // New Year's Day, Labour Day, Constitution Day, Christmas Day and Boxing Day.
+        let fixed = matches!(
+            (date.month(), date.day()),
+            (1, 1) | (5, 1) | (5, 17) | (12, 25) | (12, 26)
+        );
+
+        // Maundy Thursday, Good Friday, Easter Sunday and Monday, Ascension Day,
+        // and Whit Sunday and Monday.
+        let easter = easter_sunday(date.year());
+        let moving = [-3, -2, 0, 1, 39, 49, 50]
+            .iter()
+            .any(|days| easter + Duration::days(*days) == date);
+
+        fixed || moving
+    }
+}
+
+/// The date of Easter Sunday in the provided year, by the Gregorian calendar.
+pub fn easter_sunday(year: i32) -> NaiveDate {
+    // The anonymous Gregorian algorithm, also known as the Meeus/Jones/Butcher algorithm.
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+
+    NaiveDate::from_ymd(year, month as u32, day as u32)
+}
+
+/// How a due date falling on a weekend or a holiday is moved to a business day.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum BusinessDayConvention {
+    /// The due date is kept, regardless of business days.
+    #[default]
+    None,
+    /// The due date is moved to the following business day.
+    Following,
+    /// The due date is moved to the following business day, unless it is in the next month,
+    /// where it is moved to the preceding business day.
+    ModifiedFollowing,
+    /// The due date is moved to the preceding business day.
+    Preceding,
+}
+
+impl BusinessDayConvention {
+    /// All supported business day conventions.
+    pub const ALL: [BusinessDayConvention; 4] = [
+        BusinessDayConvention::None,
+        BusinessDayConvention::Following,
+        BusinessDayConvention::ModifiedFollowing,
+        BusinessDayConvention::Preceding,
+    ];
+
+    /// Move the date to a business day of the calendar, according to this convention.
+    pub fn adjust(&self, date: NaiveDate, calendar: &dyn HolidayCalendar) -> NaiveDate {
+        let following = || {
+            let mut day = date;
+            while !calendar.is_business_day(day) {
+                day = day.succ();
+            }
+            day
+        };
+        let preceding = || {
+            let mut day = date;
+            while !calendar.is_business_day(day) {
+                day = day.pred();
+            }
+            day
+        };
+
+        match self {
+            BusinessDayConvention::None => date,
+            BusinessDayConvention::Following => following(),
+            BusinessDayConvention::ModifiedFollowing => match following() {
+                day if day.month() == date.month() => day,
+                _ => preceding(),
+            },
+            BusinessDayConvention::Preceding => preceding(),
+        }
+    }
+}
+
+impl FromStr for BusinessDayConvention {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "none" => Ok(BusinessDayConvention::None),
+            "following" => Ok(BusinessDayConvention::Following),
+            "modified-following" => Ok(BusinessDayConvention::ModifiedFollowing),
+            "preceding" => Ok(BusinessDayConvention::Preceding),
+            _ => {
+                Err("expecting either none, following, modified-following or preceding".to_string())
+            }
+        }
+    }
+}
+
+impl fmt::Display for BusinessDayConvention {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BusinessDayConvention::None => write!(f, "none"),
+            BusinessDayConvention::Following => write!(f, "following"),
+            BusinessDayConvention::ModifiedFollowing => write!(f, "modified-following"),
+            BusinessDayConvention::Preceding => write!(f, "preceding"),
+        }
+    }
+}
@@ -1,10 +1,16 @@
 //! This module encapsulates the API used to interact with the library.
 
-use chrono::Month;
+use crate::calendar::BusinessDayConvention;
+use crate::index::IndexSeries;
+
+use chrono::{Month, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 
 /// Each variant of a LoanEvent details the various events that can occur
 /// for the lifetime of the loan.
-#[derive(Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub enum LoanEvent {
     /// The initial loan event - This is the point where the loan is constructed.
     Initial(LoanInitialization),
@@ -22,20 +28,25 @@ pub enum LoanEvent {
     Extra(LoanRecurringExtraInstallments),
     /// We schedule a installment freeze, only interest will be owed.
     RepaymentFreeze(LoanRepaymentFreeze),
+    /// The outstanding loan is repayed in full ahead of its schedule,
+    /// together with the interest accrued since the last installment.
+    /// This ends the loan, any later events are ignored.
+    EarlyPayoff(LoanPayoff),
 }
 
 impl LoanEvent {
     pub(crate) fn initial(&self) -> &LoanInitialization {
-        match &*self {
-            LoanEvent::Initial(d) => &d,
+        match self {
+            LoanEvent::Initial(d) => d,
             _ => panic!("attempted to access LoanEvent::Initial that was not initial"),
         }
     }
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum MonthlyDueDate {
     /// The 1st of the month.
+    #[default]
     First,
     /// The 15th of the month.
     Mid,
@@ -47,7 +58,7 @@ pub enum MonthlyDueDate {
 
 impl MonthlyDueDate {
     pub fn to_u32(&self) -> u32 {
-        match &*self {
+        match self {
             MonthlyDueDate::First => 1,
             MonthlyDueDate::Mid => 15,
             MonthlyDueDate::End => 31,
@@ -56,32 +67,84 @@ impl MonthlyDueDate {
     }
 }
 
+impl fmt::Display for MonthlyDueDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let day = match self {
+            MonthlyDueDate::End => return write!(f, "end of month"),
+            d => d.to_u32(),
+        };
+        let suffix = match (day % 10, day % 100) {
+            (_, 11..=13) => "th",
+            (1, _) => "st",
+            (2, _) => "nd",
+            (3, _) => "rd",
+            _ => "th",
+        };
+        write!(f, "{}{}", day, suffix)
+    }
+}
+
 /// Indicate how many installments per year a loan is is configured to have.
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum TermsPerYear {
     One,
     Two,
     Three,
     Four,
     Six,
+    #[default]
     Twelve,
+    /// Every second week, due on a fixed weekday.
+    TwentySix,
+    /// Every week, due on a fixed weekday.
+    FiftyTwo,
 }
 
 impl TermsPerYear {
     pub fn to_u32(&self) -> u32 {
-        match &*self {
+        match self {
             TermsPerYear::One => 1,
             TermsPerYear::Two => 2,
             TermsPerYear::Three => 3,
             TermsPerYear::Four => 4,
             TermsPerYear::Six => 6,
             TermsPerYear::Twelve => 12,
+            TermsPerYear::TwentySix => 26,
+            TermsPerYear::FiftyTwo => 52,
         }
     }
+
+    /// The fixed number of days between the terms of a day-based schedule,
+    /// or none when the terms are a number of months apart.
+    pub fn days_between_terms(&self) -> Option<u32> {
+        match self {
+            TermsPerYear::TwentySix => Some(14),
+            TermsPerYear::FiftyTwo => Some(7),
+            _ => None,
+        }
+    }
+
+    /// All supported number of terms per year, ordered from the fewest to the most terms.
+    pub const ALL: [TermsPerYear; 8] = [
+        TermsPerYear::One,
+        TermsPerYear::Two,
+        TermsPerYear::Three,
+        TermsPerYear::Four,
+        TermsPerYear::Six,
+        TermsPerYear::Twelve,
+        TermsPerYear::TwentySix,
+        TermsPerYear::FiftyTwo,
+    ];
+}
+
+impl fmt::Display for TermsPerYear {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_u32())
+    }
 }
 
 /// The initial state of a loan.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LoanInitialization {
     /// The total loan sum.
     pub loan: f64,
@@ -92,10 +155,12 @@ pub struct LoanInitialization {
     /// calculations.
     pub administration_fee: f64,
     /// Each installment that includes a re-payment may include an additional fee.
-    /// This fee is not included for interest installments, only re-payment installments.
+    /// This fee is not included for interest installments due to a repayment freeze,
+    /// only re-payment installments and the initial interest-only terms.
     pub installment_fee: f64,
 
-    /// The number of terms this loan should be downpayed over.
+    /// The number of terms this loan should be downpayed over,
+    /// including any initial interest-only terms.
     pub terms: u32,
     /// The number of terms per year.
     pub terms_per_year: TermsPerYear,
@@ -105,27 +170,459 @@ pub struct LoanInitialization {
     /// This is first month after payout_date that an installment is due.
     /// The date within this month is calculated based on due_within_month.
     pub first_installment_month: Month,
+    /// The weekday installments are due on, for weekly and biweekly terms.
+    /// The first installment is due on this weekday on or after the due date within the
+    /// first installment month, where the weekday of that due date is kept when not set.
+    #[serde(default)]
+    pub due_weekday: Option<Weekday>,
+    /// How a due date falling on a weekend or a holiday is moved to a business day.
+    #[serde(default)]
+    pub business_day_convention: BusinessDayConvention,
+
+    /// A residual amount of the loan that is due with the last term,
+    /// rather than being amortised over the terms of the loan.
+    #[serde(default)]
+    pub balloon: Option<Balloon>,
+    /// The number of terms at the start of the loan where only the interest and the
+    /// installment fee are payed. These terms are part of the total number of terms,
+    /// and the loan is amortised over the remaining terms.
+    #[serde(default)]
+    pub interest_only_terms: u32,
+    /// How the loan is amortised over its repayment terms.
+    #[serde(default)]
+    pub amortisation: Amortisation,
+    /// How extra installments affect the remaining repayment terms,
+    /// unless the extra installment event sets its own policy.
+    #[serde(default)]
+    pub extra_policy: ExtraInstallmentPolicy,
+
+    /// The last date of the fixed nominal interest, after which the floating rate applies.
+    #[serde(default)]
+    pub fixed_until: Option<NaiveDate>,
+    /// The floating nominal interest of the loan. This applies from the day after the
+    /// fixed-rate period, or from the disbursement when there is no fixed-rate period.
+    #[serde(default)]
+    pub floating_rate: Option<FloatingRate>,
+    /// Contractual limits on the nominal interest, applied on every change of the interest.
+    #[serde(default)]
+    pub rate_limits: RateLimits,
+}
+
+impl Default for LoanInitialization {
+    /// An empty annuity loan repayed monthly over a single year, due on the 1st of every
+    /// month from January, without any fees or changes of the nominal interest.
+    fn default() -> Self {
+        LoanInitialization {
+            loan: 0.0,
+            nominal_interest: 0.0,
+            administration_fee: 0.0,
+            installment_fee: 0.0,
+
+            terms: 12,
+            terms_per_year: TermsPerYear::default(),
+            due_within_month: MonthlyDueDate::default(),
+            first_installment_month: Month::January,
+            due_weekday: None,
+            business_day_convention: BusinessDayConvention::default(),
+            balloon: None,
+            interest_only_terms: 0,
+            amortisation: Amortisation::default(),
+            extra_policy: ExtraInstallmentPolicy::default(),
+            fixed_until: None,
+            floating_rate: None,
+            rate_limits: RateLimits::default(),
+        }
+    }
+}
+
+/// Contractual limits on the nominal interest of a floating loan.
+///
+/// A cap together with a floor forms a collar. Every interest change, including those of a
+/// floating rate, is a reset where the nominal interest may move at most the change limit.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct RateLimits {
+    /// The highest nominal interest of the loan.
+    #[serde(default)]
+    pub cap: Option<f64>,
+    /// The lowest nominal interest of the loan.
+    #[serde(default)]
+    pub floor: Option<f64>,
+    /// The largest change of the nominal interest on a single reset, in percentage points.
+    #[serde(default)]
+    pub max_change: Option<f64>,
+}
+
+/// A single contractual limit on the nominal interest.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RateLimit {
+    /// The nominal interest was held at the cap.
+    Cap,
+    /// The nominal interest was held at the floor.
+    Floor,
+    /// The change of the nominal interest was held at the change limit.
+    Change,
+}
+
+impl RateLimits {
+    /// Whether there are no limits on the nominal interest.
+    pub fn is_empty(&self) -> bool {
+        self == &RateLimits::default()
+    }
+
+    /// Validate that the limits are positive, and that the floor is not above the cap.
+    pub fn validate(&self) -> Result<(), String> {
+        let limits = [self.cap, self.floor, self.max_change];
+        if limits.iter().flatten().any(|limit| *limit <= 0.0) {
+            return Err("expecting non-zero positive interest rate limits".to_string());
+        }
+        match (self.floor, self.cap) {
+            (Some(floor), Some(cap)) if floor > cap => {
+                Err("expecting an interest rate floor no higher than the cap".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// The nominal interest applied when the requested interest takes effect,
+    /// where current is the nominal interest before the reset, if any.
+    /// The limit restricting the interest is returned together with the applied interest.
+    pub fn apply(&self, current: Option<f64>, requested: f64) -> (f64, Option<RateLimit>) {
+        let mut applied = requested;
+        let mut limit = None;
+
+        if let (Some(current), Some(max_change)) = (current, self.max_change) {
+            if (applied - current).abs() > max_change {
+                applied = current + max_change.copysign(applied - current);
+                limit = Some(RateLimit::Change);
+            }
+        }
+        if let Some(cap) = self.cap.filter(|cap| applied > *cap) {
+            applied = cap;
+            limit = Some(RateLimit::Cap);
+        }
+        if let Some(floor) = self.floor.filter(|floor| applied < *floor) {
+            applied = floor;
+            limit = Some(RateLimit::Floor);
+        }
+
+        (applied, limit)
+    }
+}
+
+impl fmt::Display for RateLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RateLimit::Cap => "cap",
+            RateLimit::Floor => "floor",
+            RateLimit::Change => "change limit",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// The floating nominal interest of a loan.
+///
+/// The calculator applies the floating rate as interest changes on the loan,
+/// where interest change events on the same date take precedence.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum FloatingRate {
+    /// A path of nominal interest rates, each effective from its date.
+    Path(Vec<(NaiveDate, f64)>),
+    /// A margin over a reference index, reset at a regular interval.
+    Indexed(IndexedRate),
+}
+
+/// A floating nominal interest priced as a margin over a reference index.
+///
+/// The nominal interest is reset at a regular interval from the first floating date,
+/// to the index rate observed a number of days before the reset, plus the margin.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct IndexedRate {
+    /// The reference index.
+    pub index: IndexSeries,
+    /// The margin added to the index rate.
+    pub margin: f64,
+    /// The interval between each reset of the nominal interest.
+    pub reset: RecurringInterval,
+    /// The number of days before each reset the index rate is observed.
+    #[serde(default)]
+    pub lookback_days: u32,
+}
+
+/// Parse dated rates formatted as `<yyyy-mm-dd>=<rate>`, separated by commas.
+pub fn parse_dated_rates(s: &str) -> Result<Vec<(NaiveDate, f64)>, String> {
+    s.split(',')
+        .filter(|entry| !entry.trim().is_empty())
+        .map(|entry| {
+            let (date, rate) = entry
+                .split_once('=')
+                .ok_or_else(|| "expecting rates formatted as yyyy-mm-dd=rate".to_string())?;
+            let date = NaiveDate::from_str(date.trim())
+                .map_err(|_| format!("expecting a date formatted as yyyy-mm-dd, got {}", date))?;
+            let rate = f64::from_str(rate.trim())
+                .map_err(|_| format!("expecting a rate, got {}", rate))?;
+            Ok((date, rate))
+        })
+        .collect()
+}
+
+/// Format dated rates as parsed by [`parse_dated_rates`].
+pub fn format_dated_rates(rates: &[(NaiveDate, f64)]) -> String {
+    rates
+        .iter()
+        .map(|(date, rate)| format!("{}={}", date, rate))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// How the loan is repayed over the repayment terms.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Amortisation {
+    /// Every term payment is the same, where the repayment portion increases
+    /// as the interest portion decreases.
+    #[default]
+    Annuity,
+    /// Every term repays the same portion of the loan, together with the interest
+    /// accrued since the last installment. The term payment decreases over time.
+    Serial,
+}
+
+impl Amortisation {
+    /// All supported amortisations.
+    pub const ALL: [Amortisation; 2] = [Amortisation::Annuity, Amortisation::Serial];
+}
+
+impl fmt::Display for Amortisation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Amortisation::Annuity => write!(f, "annuity"),
+            Amortisation::Serial => write!(f, "serial"),
+        }
+    }
+}
+
+/// How an extra installment affects the remaining repayment terms of the loan.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+pub enum ExtraInstallmentPolicy {
+    /// Keep the term payment, such that the loan is repayed in fewer terms.
+    /// For serial loans, the repayment portion of each term is kept.
+    #[default]
+    ShortenTerm,
+    /// Keep the planned terms, recomputing a lower term payment over the remaining terms.
+    LowerPayment,
+}
+
+impl ExtraInstallmentPolicy {
+    /// All supported extra installment policies.
+    pub const ALL: [ExtraInstallmentPolicy; 2] = [
+        ExtraInstallmentPolicy::ShortenTerm,
+        ExtraInstallmentPolicy::LowerPayment,
+    ];
+}
+
+impl FromStr for ExtraInstallmentPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "shorten-term" => Ok(ExtraInstallmentPolicy::ShortenTerm),
+            "lower-payment" => Ok(ExtraInstallmentPolicy::LowerPayment),
+            _ => Err("expecting either shorten-term or lower-payment".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for ExtraInstallmentPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExtraInstallmentPolicy::ShortenTerm => write!(f, "shorten term"),
+            ExtraInstallmentPolicy::LowerPayment => write!(f, "lower payment"),
+        }
+    }
+}
+
+/// The residual amount of a loan, due with the last term.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Balloon {
+    /// A fixed amount.
+    Amount(f64),
+    /// A percentage of the loan sum.
+    Percentage(f64),
+}
+
+impl Balloon {
+    /// Resolve the balloon to an amount of the provided loan sum.
+    pub fn amount_of(&self, loan: f64) -> f64 {
+        match self {
+            Balloon::Amount(amount) => *amount,
+            Balloon::Percentage(percentage) => loan * percentage / 100.0,
+        }
+    }
+}
+
+impl FromStr for Balloon {
+    type Err = String;
+
+    /// Parse a balloon as either an amount, or a percentage when suffixed by `%`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (value, percentage) = match s.strip_suffix('%') {
+            Some(value) => (value.trim(), true),
+            None => (s, false),
+        };
+        let value =
+            f64::from_str(value).map_err(|_| "expecting an amount or a percentage".to_string())?;
+
+        Ok(if percentage {
+            Balloon::Percentage(value)
+        } else {
+            Balloon::Amount(value)
+        })
+    }
+}
+
+impl fmt::Display for Balloon {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Balloon::Amount(amount) => write!(f, "{}", amount),
+            Balloon::Percentage(percentage) => write!(f, "{}%", percentage),
+        }
+    }
 }
 
 /// An event to describe the transfer of a loan from one bank to another.
 /// Terms and installment dates will be transferred from the last bank.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LoanTransfer {
     /// When issuing a loan, some banks will charge an administration fee for
     /// issuing the loan. This can be added to the loan sum and will be part of the
     /// calculations.
     pub administration_fee: f64,
+    /// The fee charged by the previous bank for repaying the outstanding loan early.
+    /// This fee is payed on the date of the transfer, and is not part of the new loan.
+    #[serde(default)]
+    pub prepayment_fee: Option<PrepaymentFee>,
+}
+
+/// An event to describe the repayment of the entire loan ahead of its schedule.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct LoanPayoff {
+    /// Some banks charge an administration fee for closing the loan.
+    /// This fee is payed on the date of the payoff, together with the outstanding loan.
+    pub administration_fee: f64,
+    /// The fee charged by the bank for repaying the outstanding loan early.
+    #[serde(default)]
+    pub prepayment_fee: Option<PrepaymentFee>,
+}
+
+/// A fee charged by the bank when the loan is repayed ahead of its schedule,
+/// such as a fixed-rate breakage fee.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum PrepaymentFee {
+    /// A fixed amount per prepayment.
+    Flat(f64),
+    /// A percentage of the prepaid amount.
+    Percentage(f64),
+    /// The interest the bank loses on the prepaid amount for the remainder of the fixed-rate
+    /// period, when the contract interest is above the current market interest.
+    RateDifferential {
+        /// The current market interest for the remainder of the fixed-rate period.
+        market_interest: f64,
+        /// The last date of the fixed-rate period.
+        fixed_until: NaiveDate,
+    },
+}
+
+impl PrepaymentFee {
+    /// Compute the fee of prepaying amount on date, while the loan has the contract interest.
+    ///
+    /// The rate differential is computed as the interest lost by the bank per year,
+    /// over the remaining days of the fixed-rate period. No fee is charged when the
+    /// market interest is above the contract interest, or after the fixed-rate period.
+    pub fn fee_of(&self, amount: f64, date: NaiveDate, contract_interest: f64) -> f64 {
+        match self {
+            PrepaymentFee::Flat(fee) => *fee,
+            PrepaymentFee::Percentage(percentage) => amount * percentage / 100.0,
+            PrepaymentFee::RateDifferential {
+                market_interest,
+                fixed_until,
+            } => {
+                let years = (*fixed_until - date).num_days().max(0) as f64 / 365.0;
+                let differential = (contract_interest - market_interest).max(0.0) / 100.0;
+                amount * differential * years
+            }
+        }
+    }
+}
+
+impl FromStr for PrepaymentFee {
+    type Err = String;
+
+    /// Parse a prepayment fee as either an amount, a percentage when suffixed by `%`,
+    /// or a rate differential formatted as `<market interest>% until <yyyy-mm-dd>`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        const EXPECTING: &str =
+            "expecting an amount, a percentage or a market interest until a date";
+
+        let s = s.trim();
+        if let Some((interest, date)) = s.split_once(" until ") {
+            let market_interest = interest
+                .trim()
+                .trim_end_matches('%')
+                .trim()
+                .parse()
+                .map_err(|_| EXPECTING.to_string())?;
+            let fixed_until =
+                NaiveDate::from_str(date.trim()).map_err(|_| EXPECTING.to_string())?;
+            return Ok(PrepaymentFee::RateDifferential {
+                market_interest,
+                fixed_until,
+            });
+        }
+
+        match s.strip_suffix('%') {
+            Some(value) => f64::from_str(value.trim()).map(PrepaymentFee::Percentage),
+            None => f64::from_str(s).map(PrepaymentFee::Flat),
+        }
+        .map_err(|_| EXPECTING.to_string())
+    }
+}
+
+impl fmt::Display for PrepaymentFee {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrepaymentFee::Flat(fee) => write!(f, "{}", fee),
+            PrepaymentFee::Percentage(percentage) => write!(f, "{}%", percentage),
+            PrepaymentFee::RateDifferential {
+                market_interest,
+                fixed_until,
+            } => write!(f, "{}% until {}", market_interest, fixed_until),
+        }
+    }
 }
 
 /// An event to describe an interest change on a loan.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LoanInterestChange {
     /// This is the new interest on the loan, effective from date.
     pub nominal_interest: f64,
 }
 
+impl LoanInterestChange {
+    /// Validate that the new interest is positive.
+    pub fn validate(&self) -> Result<(), String> {
+        if self.nominal_interest <= 0.0 {
+            return Err(
+                "expecting non-zero positive value for nominal interest in interest change event"
+                    .to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
 /// An event to describe an refinacing action.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LoanRefinance {
     /// The total to increase the loan by.
     pub loan_increase: f64,
@@ -135,13 +632,14 @@ pub struct LoanRefinance {
 }
 
 /// A recurring interval selection within a year.
-#[derive(Clone, Debug)]
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
 pub enum RecurringInterval {
     /// Every week.
     Weekly,
     /// Every two weeks.
     Biweekly,
     /// Every month
+    #[default]
     Monthly,
     /// Every two months, six times a year.
     Bimonthly,
@@ -155,8 +653,57 @@ pub enum RecurringInterval {
     Anually,
 }
 
+impl RecurringInterval {
+    /// All recurring intervals, ordered from the shortest to the longest interval.
+    pub const ALL: [RecurringInterval; 8] = [
+        RecurringInterval::Weekly,
+        RecurringInterval::Biweekly,
+        RecurringInterval::Monthly,
+        RecurringInterval::Bimonthly,
+        RecurringInterval::Quarerly,
+        RecurringInterval::Triannually,
+        RecurringInterval::Biannually,
+        RecurringInterval::Anually,
+    ];
+}
+
+impl FromStr for RecurringInterval {
+    type Err = String;
+
+    /// Parse a recurring interval by its name, as displayed.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        RecurringInterval::ALL
+            .iter()
+            .find(|interval| interval.to_string() == s.trim())
+            .copied()
+            .ok_or_else(|| {
+                let names: Vec<_> = RecurringInterval::ALL
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect();
+                format!("expecting one of {}", names.join(", "))
+            })
+    }
+}
+
+impl fmt::Display for RecurringInterval {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            RecurringInterval::Weekly => "weekly",
+            RecurringInterval::Biweekly => "biweekly",
+            RecurringInterval::Monthly => "monthly",
+            RecurringInterval::Bimonthly => "bimonthly",
+            RecurringInterval::Quarerly => "quarterly",
+            RecurringInterval::Triannually => "triannually",
+            RecurringInterval::Biannually => "biannually",
+            RecurringInterval::Anually => "annually",
+        };
+        write!(f, "{}", name)
+    }
+}
+
 /// An event to schedule a set of extra payments on the loan.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LoanRecurringExtraInstallments {
     /// The amount per extra payments
     pub amount: f64,
@@ -166,18 +713,30 @@ pub struct LoanRecurringExtraInstallments {
     pub count: std::num::NonZeroU32,
     /// The interval to issue any additional extra payments outside one.
     pub recurring_interval: RecurringInterval,
+    /// How these extra payments affect the remaining terms.
+    /// When not set, the extra installment policy of the loan applies.
+    #[serde(default)]
+    pub policy: Option<ExtraInstallmentPolicy>,
+    /// The fee charged by the bank for each of these extra payments.
+    #[serde(default)]
+    pub prepayment_fee: Option<PrepaymentFee>,
 }
 
 /// An event to add a single extra installment.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LoanExtraInstallment {
     /// The amount per extra payments
     pub amount: f64,
+    /// How this extra payment affects the remaining terms.
+    /// When not set, the extra installment policy of the loan applies.
+    pub policy: Option<ExtraInstallmentPolicy>,
+    /// The fee charged by the bank for this extra payment.
+    pub prepayment_fee: Option<PrepaymentFee>,
 }
 
 /// An event that freezes the current repayment installments.
 /// Only interest installments must be made.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct LoanRepaymentFreeze {
     /// The number of repayment installment freezes.
     pub count: std::num::NonZeroU32,
@@ -1,53 +1,68 @@
 //! Implement a user-interface application for the interest calculator.
 
+mod storage;
 mod widgets;
 
-use self::widgets::{event_initialization::EventInitialization, summary::Summary};
-use crate::{InteractiveCalculator, LoanInitialization, MonthlyDueDate, TermsPerYear};
+#[cfg(not(target_arch = "wasm32"))]
+use self::widgets::balance_chart::BalanceChart;
+#[cfg(target_arch = "wasm32")]
+use self::widgets::balance_chart_web::BalanceChart;
+use self::widgets::comparison::WidgetMessage as ComparisonMessage;
+use self::widgets::{
+    comparison::Comparison, event_initialization::EventInitialization,
+    event_timeline::EventTimeline, scenarios::Scenarios, schedule::Schedule, summary::Summary,
+};
+use crate::{InteractiveCalculator, LoanInitialization};
 
-use iced::{Column, Container, Element, Length, Sandbox};
+use iced::{scrollable, Column, Container, Element, Length, Row, Sandbox, Scrollable};
 
 /// The GUI application.
 pub struct App {
     summary: Summary,
+    schedule: Schedule,
+    chart: BalanceChart,
     calculator: InteractiveCalculator,
+    /// The other scenario when comparing two scenarios, which is not being edited.
+    other: Option<InteractiveCalculator>,
+    comparison: Comparison,
     event_initialization: EventInitialization,
+    event_timeline: EventTimeline,
+    scenarios: Scenarios,
+    scroll: scrollable::State,
 }
 
 #[derive(Debug, Clone)]
 pub enum Message {
     EventInitialization(widgets::event_initialization::WidgetMessage),
+    EventTimeline(widgets::event_timeline::WidgetMessage),
+    Schedule(widgets::schedule::WidgetMessage),
+    Scenarios(widgets::scenarios::WidgetMessage),
+    Comparison(widgets::comparison::WidgetMessage),
 }
 
 impl Sandbox for App {
     type Message = Message;
 
     fn new() -> Self {
-        // We default to today
-        let loan_start_date = chrono::Utc::today().naive_utc();
-        let first_installment_month = crate::calculator::future_month(&loan_start_date, 1);
-
-        let initial = LoanInitialization {
-            loan: 1000000.0,
-            nominal_interest: 1.25,
-            administration_fee: 0.0,
-            installment_fee: 45.0,
-
-            terms: 12,
-            terms_per_year: TermsPerYear::Twelve,
-            due_within_month: MonthlyDueDate::First,
-            first_installment_month,
-        };
+        // Continue where the last session left off
+        let mut scenarios = Scenarios::new();
+        let calculator = scenarios.restore().unwrap_or_else(Self::default_calculator);
 
-        let event_initialization = EventInitialization::new(&initial);
         let mut s = Self {
             summary: Summary::default(),
-            calculator: InteractiveCalculator::new(loan_start_date.clone(), initial),
-            event_initialization,
+            schedule: Schedule::default(),
+            chart: BalanceChart::default(),
+            event_initialization: Self::event_initialization(&calculator),
+            event_timeline: EventTimeline::new(&calculator),
+            calculator,
+            other: None,
+            comparison: Comparison::default(),
+            scenarios,
+            scroll: scrollable::State::default(),
         };
 
         // Cheeky initialization
-        s.summary.update(s.calculator.compute());
+        s.refresh();
 
         s
     }
@@ -59,28 +74,75 @@ impl Sandbox for App {
     fn update(&mut self, event: Message) {
         match event {
             Message::EventInitialization(m) => {
-                self.event_initialization.update(&mut self.calculator, 0, m)
+                self.event_initialization.update(&mut self.calculator, 0, m);
+                self.event_timeline.sync(&self.calculator);
+            }
+            Message::EventTimeline(m) => self.event_timeline.update(&mut self.calculator, m),
+            Message::Scenarios(m) => {
+                if self.scenarios.update(&mut self.calculator, m) {
+                    // A different scenario was loaded, start editing it from scratch.
+                    self.event_initialization = Self::event_initialization(&self.calculator);
+                    self.event_timeline = EventTimeline::new(&self.calculator);
+                }
+            }
+            Message::Comparison(m) => {
+                self.comparison.on_message(&m);
+                match m {
+                    ComparisonMessage::Compare => self.other = Some(self.calculator.clone()),
+                    ComparisonMessage::Switch => {
+                        if let Some(other) = self.other.as_mut() {
+                            std::mem::swap(&mut self.calculator, other);
+                            self.event_initialization =
+                                Self::event_initialization(&self.calculator);
+                            self.event_timeline = EventTimeline::new(&self.calculator);
+                        }
+                    }
+                    ComparisonMessage::Stop => self.other = None,
+                }
+            }
+            Message::Schedule(m) => {
+                // Only the presentation of the schedule is altered.
+                self.schedule.on_message(m);
+                return;
             }
         }
 
-        self.summary.update(self.calculator.compute());
+        self.refresh();
     }
 
     fn view(&mut self) -> Element<Message> {
         // Left hand column that holds:
         // * Overall total loan statistics
+        // * The comparison against another scenario
         // * The events modification table
-        let content: Element<_> = Column::new()
-            .max_width(540)
+        // * The timeline of all other events
+        // * The saved scenarios
+        let column = Column::new()
+            .max_width(720)
             .spacing(20)
             .padding(20)
             .push(self.summary.view())
+            .push(self.comparison.view(self.other.is_some()))
             .push(
                 self.event_initialization
                     .view()
                     .map(Message::EventInitialization),
             )
-            .into();
+            .push(self.event_timeline.view().map(Message::EventTimeline))
+            .push(self.scenarios.view().map(Message::Scenarios));
+        let left = Scrollable::new(&mut self.scroll)
+            .width(Length::FillPortion(2))
+            .push(column);
+
+        // Right hand column holds the chart and the schedule of all installments.
+        let right = Column::new()
+            .padding(20)
+            .spacing(20)
+            .width(Length::FillPortion(3))
+            .push(self.chart.view())
+            .push(self.schedule.view());
+
+        let content: Element<_> = Row::new().push(left).push(right).into();
 
         // Generate the top level view
         Container::new(content)
@@ -90,3 +152,65 @@ impl Sandbox for App {
             .into()
     }
 }
+
+impl App {
+    /// A loan disbursed today, used when there is no scenario to restore.
+    fn default_calculator() -> InteractiveCalculator {
+        // We default to today
+        let loan_start_date = chrono::Utc::today().naive_utc();
+        let first_installment_month = crate::calculator::future_month(&loan_start_date, 1);
+
+        let initial = LoanInitialization {
+            loan: 1000000.0,
+            nominal_interest: 1.25,
+            installment_fee: 45.0,
+            terms: 12,
+            first_installment_month,
+            ..LoanInitialization::default()
+        };
+
+        InteractiveCalculator::new(loan_start_date, initial)
+    }
+
+    fn event_initialization(calculator: &InteractiveCalculator) -> EventInitialization {
+        // SAFETY(unwrap): the calculator always contains the initial event.
+        let (date, initial) = calculator.events().next().unwrap();
+        EventInitialization::new(date, initial.initial())
+    }
+
+    /// Recompute the calculator and update every widget presenting the results.
+    fn refresh(&mut self) {
+        self.summary.update(&self.calculator);
+        self.schedule.update(self.calculator.ledger());
+        self.scenarios.autosave(&self.calculator);
+        self.comparison
+            .update(&self.calculator, self.other.as_ref());
+
+        self.chart.update(self.calculator.ledger());
+        let overlays = match self.other.as_ref() {
+            Some(other) => other
+                .ledger()
+                .map(|ledger| vec![(self.comparison.other_name().to_string(), ledger)])
+                .unwrap_or_default(),
+            None => self.original_plan().into_iter().collect(),
+        };
+        self.chart.update_overlays(overlays);
+    }
+
+    /// The ledger of the loan without any of the later events, as initially planned.
+    /// Nothing is returned when there are no later events to compare against.
+    fn original_plan(&self) -> Option<(String, Vec<crate::Daily>)> {
+        if self.calculator.events().count() < 2 {
+            return None;
+        }
+
+        let initial = match self.calculator.events().next() {
+            Some((_, crate::LoanEvent::Initial(initial))) => initial.clone(),
+            _ => return None,
+        };
+        let plan = InteractiveCalculator::new(self.calculator.disbursement_date(), initial);
+        plan.ledger()
+            .ok()
+            .map(|ledger| ("Original plan".to_string(), ledger))
+    }
+}
@@ -0,0 +1,137 @@
+//! Persistence of the scenarios of the application between sessions.
+//!
+//! The web application keeps the scenarios in the browser local storage,
+//! while the native application keeps them in a file in the home directory.
+
+use crate::Scenario;
+
+use serde::{Deserialize, Serialize};
+
+/// The key or file name the store is persisted under.
+const STORE_NAME: &str = "interest-calculator-scenarios";
+
+/// Every scenario of the application.
+#[derive(Default, Deserialize, Serialize)]
+pub struct Store {
+    /// The scenario being edited, restored when the application starts.
+    pub current: Option<Scenario>,
+    /// The scenarios saved by the user, which they can switch between.
+    pub saved: Vec<Scenario>,
+}
+
+impl Store {
+    /// Read the persisted store. A store that was never persisted is empty.
+    pub fn load() -> Result<Store, String> {
+        match read()? {
+            Some(json) => {
+                serde_json::from_str(&json).map_err(|e| format!("invalid saved scenarios: {}", e))
+            }
+            None => Ok(Store::default()),
+        }
+    }
+
+    pub fn persist(&self) -> Result<(), String> {
+        write(&serde_json::to_string(self).map_err(|e| e.to_string())?)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn path() -> std::path::PathBuf {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    let file = format!(".{}.json", STORE_NAME);
+    match home {
+        Some(home) => std::path::Path::new(&home).join(file),
+        None => std::path::PathBuf::from(file),
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn read() -> Result<Option<String>, String> {
+    let path = path();
+    if !path.exists() {
+        return Ok(None);
+    }
+    std::fs::read_to_string(&path)
+        .map(Some)
+        .map_err(|e| format!("failed to read {}: {}", path.display(), e))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn write(json: &str) -> Result<(), String> {
+    let path = path();
+    std::fs::write(&path, json).map_err(|e| format!("failed to write {}: {}", path.display(), e))
+}
+
+#[cfg(target_arch = "wasm32")]
+fn local_storage() -> Result<web_sys::Storage, String> {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .ok_or_else(|| "local storage is not available".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn read() -> Result<Option<String>, String> {
+    local_storage()?
+        .get_item(STORE_NAME)
+        .map_err(|_| "failed to read from local storage".to_string())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn write(json: &str) -> Result<(), String> {
+    local_storage()?
+        .set_item(STORE_NAME, json)
+        .map_err(|_| "failed to write to local storage".to_string())
+}
+
+/// Export a scenario to the file at path.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export(scenario: &Scenario, path: &str) -> Result<(), String> {
+    scenario.save(std::path::Path::new(path))
+}
+
+/// Import a scenario from the file at path.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn import(path: &str) -> Result<Scenario, String> {
+    Scenario::load(std::path::Path::new(path))
+}
+
+/// Export a scenario by letting the browser download it as a file named file_name.
+#[cfg(target_arch = "wasm32")]
+pub fn export(scenario: &Scenario, file_name: &str) -> Result<(), String> {
+    use wasm_bindgen::JsCast;
+
+    let href = format!(
+        "data:application/json;charset=utf-8,{}",
+        percent_encode(&scenario.to_json()?)
+    );
+    let anchor = web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.create_element("a").ok())
+        .and_then(|a| a.dyn_into::<web_sys::HtmlElement>().ok())
+        .ok_or_else(|| "failed to create the download link".to_string())?;
+    anchor
+        .set_attribute("href", &href)
+        .and_then(|_| anchor.set_attribute("download", file_name))
+        .map_err(|_| "failed to create the download link".to_string())?;
+    anchor.click();
+
+    Ok(())
+}
+
+/// Import a scenario from its JSON content, as the browser cannot read arbitrary files.
+#[cfg(target_arch = "wasm32")]
+pub fn import(json: &str) -> Result<Scenario, String> {
+    Scenario::from_json(json)
+}
+
+#[cfg(target_arch = "wasm32")]
+fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
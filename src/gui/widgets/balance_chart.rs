@@ -0,0 +1,431 @@
+//! A chart widget drawing the progress of the loan over its lifetime.
+//!
+//! The chart is drawn on an iced canvas, which is not available on the web,
+//! where the chart is rendered as an image by [`super::balance_chart_web`].
+
+use crate::gui::Message;
+use crate::reports::{Daily, ScheduleEntry};
+
+use chrono::{Datelike, NaiveDate};
+use iced::canvas::{self, Cache, Canvas, Cursor, Frame, Geometry, Path, Program, Stroke};
+use iced::{
+    Color, Element, HorizontalAlignment, Length, Point, Rectangle, Size, Text, VerticalAlignment,
+};
+
+/// The outstanding balance of the primary scenario is drawn in this color.
+const BALANCE_COLOR: Color = Color::from_rgb(0.76, 0.094, 0.027);
+/// The cumulative cost of the primary scenario is drawn in this color.
+const COST_COLOR: Color = Color::from_rgb(0.0, 0.35, 0.75);
+/// Overlay scenarios cycle through these colors.
+const OVERLAY_COLORS: [Color; 3] = [
+    Color::from_rgb(0.2, 0.6, 0.2),
+    Color::from_rgb(0.6, 0.4, 0.0),
+    Color::from_rgb(0.5, 0.2, 0.6),
+];
+
+/// Space reserved around the plot for the axis labels.
+const MARGIN_LEFT: f32 = 90.0;
+const MARGIN_BOTTOM: f32 = 30.0;
+const MARGIN: f32 = 10.0;
+
+/// This chart widget draws the outstanding balance and the cumulative cost
+/// of the loan for every installment in the schedule.
+///
+/// The primary scenario is drawn with both curves, while any overlay scenarios
+/// are drawn as thinner lines on the same axes. Hovering over the chart shows
+/// the details of the installment closest to the cursor.
+#[derive(Default)]
+pub struct BalanceChart {
+    primary: Option<Series>,
+    overlays: Vec<Series>,
+
+    cache: Cache,
+    error: String,
+}
+
+/// A single scenario drawn on the chart.
+struct Series {
+    name: String,
+    entries: Vec<ScheduleEntry>,
+    /// The sum of all payments up to and including each entry.
+    cumulative_cost: Vec<f64>,
+}
+
+impl Series {
+    fn from_ledger(name: String, ledger: &[Daily]) -> Self {
+        let entries = ScheduleEntry::from_ledger(ledger);
+        let cumulative_cost = entries
+            .iter()
+            .scan(0.0, |cost, e| {
+                *cost += e.payment;
+                Some(*cost)
+            })
+            .collect();
+
+        Series {
+            name,
+            entries,
+            cumulative_cost,
+        }
+    }
+
+    /// Find the index of the entry closest to date.
+    fn nearest(&self, date: NaiveDate) -> Option<usize> {
+        let after = self.entries.partition_point(|e| e.date < date);
+        let before = after.checked_sub(1);
+        match (before, self.entries.get(after)) {
+            (Some(b), Some(a)) if a.date - date < date - self.entries[b].date => Some(after),
+            (Some(b), _) => Some(b),
+            (None, Some(_)) => Some(after),
+            (None, None) => None,
+        }
+    }
+
+    /// The balance of the loan at date, as of the last entry on or before date.
+    fn balance_at(&self, date: NaiveDate) -> Option<f64> {
+        let after = self.entries.partition_point(|e| e.date <= date);
+        after
+            .checked_sub(1)
+            .map(|i| self.entries[i].outstanding_loan.max(0.0))
+    }
+}
+
+impl BalanceChart {
+    pub fn update(&mut self, ledger: Result<Vec<Daily>, String>) {
+        match ledger {
+            Ok(ledger) => {
+                self.primary = Some(Series::from_ledger("Current".to_string(), &ledger));
+                self.error.clear();
+            }
+            Err(e) => {
+                self.primary = None;
+                self.error = e;
+            }
+        }
+        self.redraw();
+    }
+
+    /// Replace the scenarios drawn as overlay lines on top of the primary scenario.
+    pub fn update_overlays(&mut self, overlays: Vec<(String, Vec<Daily>)>) {
+        self.overlays = overlays
+            .into_iter()
+            .map(|(name, ledger)| Series::from_ledger(name, &ledger))
+            .collect();
+        self.redraw();
+    }
+
+    fn redraw(&mut self) {
+        self.cache.clear();
+    }
+
+    pub fn view(&mut self) -> Element<Message> {
+        if !self.error.is_empty() {
+            return Text::new(&self.error).color(BALANCE_COLOR).into();
+        }
+
+        Canvas::new(self)
+            .width(Length::Fill)
+            .height(Length::Units(300))
+            .into()
+    }
+
+    /// All series on the chart, starting with the primary scenario.
+    fn series(&self) -> impl Iterator<Item = &Series> {
+        self.primary.iter().chain(self.overlays.iter())
+    }
+
+    /// The date range and the largest amount of all series on the chart.
+    fn bounds(&self) -> Option<(NaiveDate, NaiveDate, f64)> {
+        let first = self
+            .series()
+            .filter_map(|s| s.entries.first())
+            .map(|e| e.date)
+            .min()?;
+        let last = self
+            .series()
+            .filter_map(|s| s.entries.last())
+            .map(|e| e.date)
+            .max()?;
+        let max = self
+            .series()
+            .flat_map(|s| {
+                s.entries
+                    .iter()
+                    .map(|e| e.outstanding_loan)
+                    .chain(s.cumulative_cost.iter().copied())
+            })
+            .fold(0.0, f64::max);
+
+        Some((first, last, max))
+    }
+}
+
+/// Maps dates and amounts onto the plotting area of the canvas.
+struct Scale {
+    first: NaiveDate,
+    days: f32,
+    max: f32,
+    plot: Rectangle,
+}
+
+impl Scale {
+    fn new(chart: &BalanceChart, size: Size) -> Option<Self> {
+        let (first, last, max) = chart.bounds()?;
+        Some(Scale {
+            first,
+            days: ((last - first).num_days() as f32).max(1.0),
+            max: (max as f32 * 1.05).max(1.0),
+            plot: Rectangle {
+                x: MARGIN_LEFT,
+                y: MARGIN,
+                width: (size.width - MARGIN_LEFT - MARGIN).max(1.0),
+                height: (size.height - MARGIN_BOTTOM - MARGIN).max(1.0),
+            },
+        })
+    }
+
+    fn point(&self, date: NaiveDate, amount: f64) -> Point {
+        Point::new(self.x(date), self.y(amount))
+    }
+
+    fn x(&self, date: NaiveDate) -> f32 {
+        self.plot.x + (date - self.first).num_days() as f32 / self.days * self.plot.width
+    }
+
+    fn y(&self, amount: f64) -> f32 {
+        self.plot.y + self.plot.height - amount as f32 / self.max * self.plot.height
+    }
+
+    fn date(&self, x: f32) -> NaiveDate {
+        let days = (x - self.plot.x) / self.plot.width * self.days;
+        self.first + chrono::Duration::days(days.round() as i64)
+    }
+}
+
+impl Program<Message> for BalanceChart {
+    fn draw(&self, bounds: Rectangle, cursor: Cursor) -> Vec<Geometry> {
+        let scale = match Scale::new(self, bounds.size()) {
+            Some(scale) => scale,
+            None => return Vec::new(),
+        };
+
+        let chart = self.cache.draw(bounds.size(), |frame| {
+            draw_axes(frame, &scale);
+
+            for (overlay, color) in self.overlays.iter().zip(OVERLAY_COLORS.iter().cycle()) {
+                draw_series(frame, &scale, overlay, *color, *color, 1.0);
+            }
+            if let Some(primary) = &self.primary {
+                draw_series(frame, &scale, primary, BALANCE_COLOR, COST_COLOR, 2.0);
+            }
+
+            draw_legend(frame, self);
+        });
+
+        let mut geometry = vec![chart];
+        if let Some(position) = cursor.position_in(&bounds) {
+            if scale.plot.contains(position) {
+                let mut frame = Frame::new(bounds.size());
+                draw_hover(&mut frame, &scale, self, scale.date(position.x));
+                geometry.push(frame.into_geometry());
+            }
+        }
+
+        geometry
+    }
+}
+
+fn draw_axes(frame: &mut Frame, scale: &Scale) {
+    let plot = scale.plot;
+    let bottom = plot.y + plot.height;
+    let axis = Stroke::default().with_color(Color::from_rgb(0.4, 0.4, 0.4));
+    let grid = Stroke::default().with_color(Color::from_rgb(0.9, 0.9, 0.9));
+
+    // Horizontal grid lines with the amount on the left hand side.
+    let ticks = 5;
+    for tick in 0..=ticks {
+        let amount = scale.max as f64 * tick as f64 / ticks as f64;
+        let y = scale.y(amount);
+        if tick > 0 {
+            frame.stroke(
+                &Path::line(Point::new(plot.x, y), Point::new(plot.x + plot.width, y)),
+                grid,
+            );
+        }
+        frame.fill_text(canvas::Text {
+            content: format!("{:.0}", amount),
+            position: Point::new(plot.x - 5.0, y),
+            size: 14.0,
+            horizontal_alignment: HorizontalAlignment::Right,
+            vertical_alignment: VerticalAlignment::Center,
+            ..canvas::Text::default()
+        });
+    }
+
+    // A label at the start of every year, thinned out for long loans.
+    let last = scale.date(plot.x + plot.width);
+    let years = (last.year() - scale.first.year() + 1).max(1);
+    let step = (years as usize).div_ceil(10);
+    for year in (scale.first.year() + 1..=last.year()).step_by(step) {
+        let x = scale.x(NaiveDate::from_ymd(year, 1, 1));
+        frame.stroke(
+            &Path::line(Point::new(x, bottom), Point::new(x, bottom + 5.0)),
+            axis,
+        );
+        frame.fill_text(canvas::Text {
+            content: year.to_string(),
+            position: Point::new(x, bottom + 7.0),
+            size: 14.0,
+            horizontal_alignment: HorizontalAlignment::Center,
+            ..canvas::Text::default()
+        });
+    }
+
+    frame.stroke(
+        &Path::line(Point::new(plot.x, plot.y), Point::new(plot.x, bottom)),
+        axis,
+    );
+    frame.stroke(
+        &Path::line(
+            Point::new(plot.x, bottom),
+            Point::new(plot.x + plot.width, bottom),
+        ),
+        axis,
+    );
+}
+
+fn draw_series(
+    frame: &mut Frame,
+    scale: &Scale,
+    series: &Series,
+    balance_color: Color,
+    cost_color: Color,
+    width: f32,
+) {
+    let line = |amounts: &mut dyn Iterator<Item = (NaiveDate, f64)>| {
+        Path::new(|b| {
+            if let Some((date, amount)) = amounts.next() {
+                b.move_to(scale.point(date, amount));
+            }
+            for (date, amount) in amounts {
+                b.line_to(scale.point(date, amount));
+            }
+        })
+    };
+
+    let balance = line(
+        &mut series
+            .entries
+            .iter()
+            .map(|e| (e.date, e.outstanding_loan.max(0.0))),
+    );
+    let cost = line(
+        &mut series
+            .entries
+            .iter()
+            .zip(series.cumulative_cost.iter())
+            .map(|(e, c)| (e.date, *c)),
+    );
+
+    let stroke = Stroke::default().with_width(width);
+    frame.stroke(&balance, stroke.with_color(balance_color));
+    frame.stroke(&cost, stroke.with_color(cost_color));
+}
+
+fn draw_legend(frame: &mut Frame, chart: &BalanceChart) {
+    let mut labels = vec![
+        ("Balance".to_string(), BALANCE_COLOR),
+        ("Cumulative cost".to_string(), COST_COLOR),
+    ];
+    labels.extend(
+        chart
+            .overlays
+            .iter()
+            .zip(OVERLAY_COLORS.iter().cycle())
+            .map(|(s, color)| (s.name.clone(), *color)),
+    );
+
+    let x = frame.width() - MARGIN - 150.0;
+    for (i, (label, color)) in labels.into_iter().enumerate() {
+        let y = MARGIN + 5.0 + i as f32 * 18.0;
+        frame.fill_rectangle(Point::new(x, y + 4.0), Size::new(12.0, 8.0), color);
+        frame.fill_text(canvas::Text {
+            content: label,
+            position: Point::new(x + 18.0, y),
+            size: 14.0,
+            ..canvas::Text::default()
+        });
+    }
+}
+
+/// Highlight the installment closest to date and list its details.
+fn draw_hover(frame: &mut Frame, scale: &Scale, chart: &BalanceChart, date: NaiveDate) {
+    let primary = match &chart.primary {
+        Some(primary) => primary,
+        None => return,
+    };
+    let index = match primary.nearest(date) {
+        Some(index) => index,
+        None => return,
+    };
+    let entry = &primary.entries[index];
+    let plot = scale.plot;
+
+    let x = scale.x(entry.date);
+    frame.stroke(
+        &Path::line(Point::new(x, plot.y), Point::new(x, plot.y + plot.height)),
+        Stroke::default().with_color(Color::from_rgb(0.6, 0.6, 0.6)),
+    );
+    frame.fill(
+        &Path::circle(
+            scale.point(entry.date, entry.outstanding_loan.max(0.0)),
+            4.0,
+        ),
+        BALANCE_COLOR,
+    );
+    frame.fill(
+        &Path::circle(scale.point(entry.date, primary.cumulative_cost[index]), 4.0),
+        COST_COLOR,
+    );
+
+    let mut lines = vec![
+        entry.date.to_string(),
+        format!("Payment: {:.2}", entry.payment),
+        format!("Interest: {:.2}", entry.interest),
+        format!("Principal: {:.2}", entry.principal),
+        format!("Fee: {:.2}", entry.fee),
+        format!("Extra: {:.2}", entry.extra),
+        format!("Balance: {:.2}", entry.outstanding_loan.max(0.0)),
+    ];
+    for overlay in chart.overlays.iter() {
+        if let Some(balance) = overlay.balance_at(entry.date) {
+            lines.push(format!("{}: {:.2}", overlay.name, balance));
+        }
+    }
+
+    // Keep the details box within the plot, flipping it to the left of the cursor line.
+    let size = Size::new(200.0, 8.0 + lines.len() as f32 * 18.0);
+    let left = if x + 10.0 + size.width > plot.x + plot.width {
+        x - 10.0 - size.width
+    } else {
+        x + 10.0
+    };
+    let top = plot.y + 5.0;
+    frame.fill_rectangle(
+        Point::new(left, top),
+        size,
+        Color::from_rgba(1.0, 1.0, 1.0, 0.9),
+    );
+    frame.stroke(
+        &Path::rectangle(Point::new(left, top), size),
+        Stroke::default().with_color(Color::from_rgb(0.6, 0.6, 0.6)),
+    );
+    for (i, line) in lines.into_iter().enumerate() {
+        frame.fill_text(canvas::Text {
+            content: line,
+            position: Point::new(left + 6.0, top + 4.0 + i as f32 * 18.0),
+            size: 14.0,
+            ..canvas::Text::default()
+        });
+    }
+}
@@ -0,0 +1,88 @@
+//! A chart widget drawing the progress of the loan over its lifetime on the web.
+//!
+//! The iced canvas is not available on the web, so the chart is rendered
+//! as an SVG image instead, without the details of the hovered installment.
+
+use crate::gui::Message;
+use crate::reports::{draw_balance, Daily};
+
+use iced::{Color, Element, Image, Length, Text};
+use plotters::prelude::*;
+
+/// The size the chart is rendered in, before it is scaled to the width of the column.
+const SIZE: (u32, u32) = (800, 300);
+
+/// This chart widget draws the outstanding balance and the cumulative cost
+/// of the loan for every installment in the schedule, along with the balance
+/// of any overlay scenarios.
+#[derive(Default)]
+pub struct BalanceChart {
+    ledger: Vec<Daily>,
+    overlays: Vec<(String, Vec<Daily>)>,
+
+    /// The rendered chart, as a data URL of the SVG image.
+    image: String,
+    error: String,
+}
+
+impl BalanceChart {
+    pub fn update(&mut self, ledger: Result<Vec<Daily>, String>) {
+        match ledger {
+            Ok(ledger) => {
+                self.ledger = ledger;
+                self.error.clear();
+            }
+            Err(e) => {
+                self.ledger.clear();
+                self.error = e;
+            }
+        }
+        self.redraw();
+    }
+
+    /// Replace the scenarios drawn as overlay lines on top of the primary scenario.
+    pub fn update_overlays(&mut self, overlays: Vec<(String, Vec<Daily>)>) {
+        self.overlays = overlays;
+        self.redraw();
+    }
+
+    fn redraw(&mut self) {
+        self.image.clear();
+        if !self.error.is_empty() {
+            return;
+        }
+
+        let mut svg = String::new();
+        let drawn = {
+            let root = SVGBackend::with_string(&mut svg, SIZE).into_drawing_area();
+            draw_balance(&root, &self.ledger, &self.overlays)
+        };
+        match drawn {
+            Ok(()) => self.image = format!("data:image/svg+xml,{}", percent_encode(&svg)),
+            Err(e) => self.error = e,
+        }
+    }
+
+    pub fn view(&mut self) -> Element<Message> {
+        if !self.error.is_empty() {
+            return Text::new(&self.error)
+                .color(Color::from_rgb(0.76, 0.094, 0.027))
+                .into();
+        }
+
+        Image::new(self.image.as_str()).width(Length::Fill).into()
+    }
+}
+
+/// Encode the content of a data URL, leaving only the unreserved characters as is.
+fn percent_encode(content: &str) -> String {
+    content
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
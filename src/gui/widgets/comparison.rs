@@ -0,0 +1,212 @@
+//! A comparison widget presenting two loan scenarios side by side.
+
+use crate::gui::Message;
+use crate::InteractiveCalculator;
+
+use iced::{button, Button, Color, Column, Element, HorizontalAlignment, Length, Row, Text};
+
+/// The comparison widget is used to clone the scenario being edited into a second scenario.
+/// Either scenario can then be edited, while the differences between them are listed.
+///
+/// The information compared is:
+/// * Total cost
+/// * Total interest
+/// * End date
+/// * Effective annual rate
+#[derive(Default)]
+pub struct Comparison {
+    /// Whether the first scenario is the one being edited.
+    editing_first: bool,
+    rows: Vec<ComparisonRow>,
+
+    compare_state: button::State,
+    switch_state: button::State,
+    stop_state: button::State,
+
+    error: String,
+}
+
+/// A single compared value, formatted for both scenarios.
+struct ComparisonRow {
+    label: &'static str,
+    first: String,
+    second: String,
+    difference: String,
+}
+
+#[derive(Clone, Debug)]
+pub enum WidgetMessage {
+    /// Clone the scenario being edited into a second scenario.
+    Compare,
+    /// Edit the other scenario.
+    Switch,
+    /// Drop the scenario not being edited.
+    Stop,
+}
+
+/// The compared values of a single scenario.
+struct Figures {
+    total_cost: f64,
+    total_interest: f64,
+    end_date: chrono::NaiveDate,
+    effective_rate: Option<f64>,
+}
+
+impl Figures {
+    fn compute(calc: &InteractiveCalculator) -> Result<Figures, String> {
+        let total = calc.compute()?;
+
+        Ok(Figures {
+            total_cost: total.total_cost,
+            total_interest: total.total_interest,
+            end_date: total.end_date,
+            effective_rate: total.effective_annual_rate,
+        })
+    }
+}
+
+impl Comparison {
+    pub fn on_message(&mut self, message: &WidgetMessage) {
+        match message {
+            WidgetMessage::Compare => self.editing_first = true,
+            WidgetMessage::Switch => self.editing_first = !self.editing_first,
+            WidgetMessage::Stop => self.rows.clear(),
+        }
+    }
+
+    /// Compare the scenario being edited against the other scenario.
+    /// Nothing is compared when there is no other scenario.
+    pub fn update(
+        &mut self,
+        editing: &InteractiveCalculator,
+        other: Option<&InteractiveCalculator>,
+    ) {
+        self.rows.clear();
+        self.error.clear();
+
+        let other = match other {
+            Some(other) => other,
+            None => return,
+        };
+        let (first, second) = if self.editing_first {
+            (editing, other)
+        } else {
+            (other, editing)
+        };
+        let (first, second) = match (Figures::compute(first), Figures::compute(second)) {
+            (Ok(first), Ok(second)) => (first, second),
+            (Err(e), _) | (_, Err(e)) => {
+                self.error = e;
+                return;
+            }
+        };
+
+        let amount = |label, first: f64, second: f64| ComparisonRow {
+            label,
+            first: format!("{:.2}", first),
+            second: format!("{:.2}", second),
+            difference: format!("{:+.2}", second - first),
+        };
+        self.rows = vec![
+            amount("Total cost", first.total_cost, second.total_cost),
+            amount("Interest", first.total_interest, second.total_interest),
+            ComparisonRow {
+                label: "End date",
+                first: first.end_date.to_string(),
+                second: second.end_date.to_string(),
+                difference: format!("{:+} days", (second.end_date - first.end_date).num_days()),
+            },
+            ComparisonRow {
+                label: "Effective rate",
+                first: format_rate(first.effective_rate),
+                second: format_rate(second.effective_rate),
+                difference: match (first.effective_rate, second.effective_rate) {
+                    (Some(first), Some(second)) => format!("{:+.3} %", second - first),
+                    _ => "-".to_string(),
+                },
+            },
+        ];
+    }
+
+    /// The name of the scenario not being edited.
+    pub fn other_name(&self) -> &'static str {
+        if self.editing_first {
+            "Scenario B"
+        } else {
+            "Scenario A"
+        }
+    }
+
+    pub fn view(&mut self, comparing: bool) -> Element<Message> {
+        let mut content = Column::new()
+            .spacing(10)
+            // Render the top title
+            .push(Text::new("Comparison").size(30))
+            .push(Text::new(&self.error).color(Color::from_rgb(0.76, 0.094, 0.027)));
+
+        if !comparing {
+            return content
+                .push(
+                    Button::new(
+                        &mut self.compare_state,
+                        Text::new("Compare with a copy of this scenario"),
+                    )
+                    .on_press(Message::Comparison(WidgetMessage::Compare)),
+                )
+                .into();
+        }
+
+        let editing = if self.editing_first { "A" } else { "B" };
+        let discard = format!("Discard {}", self.other_name());
+        content = content
+            .push(Text::new(format!("Editing scenario {}", editing)))
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(
+                        Button::new(&mut self.switch_state, Text::new("Edit the other scenario"))
+                            .on_press(Message::Comparison(WidgetMessage::Switch)),
+                    )
+                    .push(
+                        Button::new(&mut self.stop_state, Text::new(discard))
+                            .on_press(Message::Comparison(WidgetMessage::Stop)),
+                    ),
+            )
+            .push(text_row(["", "Scenario A", "Scenario B", "Difference"]));
+
+        for row in self.rows.iter() {
+            content = content.push(text_row([
+                row.label,
+                &row.first,
+                &row.second,
+                &row.difference,
+            ]));
+        }
+
+        // Finalize
+        content.into()
+    }
+}
+
+/// Format an effective rate, which is not present when it is out of range.
+fn format_rate(rate: Option<f64>) -> String {
+    rate.map_or("-".to_string(), |r| format!("{:.3} %", r))
+}
+
+fn text_row<'a>(columns: [&str; 4]) -> Row<'a, Message> {
+    columns
+        .iter()
+        .enumerate()
+        .fold(Row::new().spacing(10), |row, (i, text)| {
+            let alignment = if i == 0 {
+                HorizontalAlignment::Left
+            } else {
+                HorizontalAlignment::Right
+            };
+            row.push(
+                Text::new(*text)
+                    .width(Length::Units(130))
+                    .horizontal_alignment(alignment),
+            )
+        })
+}
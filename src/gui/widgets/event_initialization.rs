@@ -1,27 +1,149 @@
 //! Represent the widget to modify the initialization state
 
-use crate::{events::LoanInitialization, InteractiveCalculator, LoanEvent};
+use super::selection::Selection;
+use crate::{
+    events::LoanInitialization, format_dated_rates, parse_dated_rates, Amortisation, Balloon,
+    BusinessDayConvention, ExtraInstallmentPolicy, FloatingRate, IndexSeries, IndexedRate,
+    InteractiveCalculator, LoanEvent, MonthlyDueDate, RateLimits, RecurringInterval, TermsPerYear,
+};
 
-use chrono::NaiveDate;
-use iced::{Column, Element, Length, Row, Space, Text, TextInput};
+use chrono::{Month, NaiveDate, Weekday};
+use iced::{text_input, Color, Column, Element, Length, Radio, Row, Space, Text, TextInput};
+use num_traits::FromPrimitive;
+use std::fmt;
 use std::str::FromStr;
 
 #[derive(Default)]
 pub struct EventInitialization {
-    administration_fee_state: iced::text_input::State,
+    administration_fee_state: text_input::State,
     administration_fee_data: String,
+    administration_fee_error: String,
 
-    disbursement_date_state: iced::text_input::State,
+    disbursement_date_state: text_input::State,
     disbursement_date_data: String,
+    disbursement_date_error: String,
 
-    installment_fee_state: iced::text_input::State,
+    installment_fee_state: text_input::State,
     installment_fee_data: String,
+    installment_fee_error: String,
 
-    loan_state: iced::text_input::State,
+    loan_state: text_input::State,
     loan_data: String,
+    loan_error: String,
 
-    interest_state: iced::text_input::State,
+    interest_state: text_input::State,
     interest_data: String,
+    interest_error: String,
+
+    /// The duration is entered either as years or as terms, based on this unit.
+    duration_unit: DurationUnit,
+    duration_state: text_input::State,
+    duration_data: String,
+    duration_error: String,
+
+    terms_per_year: TermsPerYear,
+    terms_per_year_selection: Selection<TermsPerYear>,
+
+    due_within_month: MonthlyDueDate,
+    due_within_month_options: Vec<MonthlyDueDate>,
+    due_within_month_selection: Selection<MonthlyDueDate>,
+
+    first_installment_month: MonthOption,
+    first_installment_month_options: Vec<MonthOption>,
+    first_installment_month_selection: Selection<MonthOption>,
+
+    due_weekday: WeekdayOption,
+    due_weekday_options: Vec<WeekdayOption>,
+    due_weekday_selection: Selection<WeekdayOption>,
+
+    business_day_convention: BusinessDayConvention,
+
+    balloon_state: text_input::State,
+    balloon_data: String,
+    balloon_error: String,
+
+    interest_only_terms_state: text_input::State,
+    interest_only_terms_data: String,
+    interest_only_terms_error: String,
+
+    amortisation: Amortisation,
+    extra_policy: ExtraInstallmentPolicy,
+
+    fixed_until_state: text_input::State,
+    fixed_until_data: String,
+    fixed_until_error: String,
+
+    floating_rates_state: text_input::State,
+    floating_rates_data: String,
+    floating_rates_error: String,
+
+    margin_state: text_input::State,
+    margin_data: String,
+    margin_error: String,
+
+    index_reset: RecurringInterval,
+    index_reset_selection: Selection<RecurringInterval>,
+
+    lookback_state: text_input::State,
+    lookback_data: String,
+    lookback_error: String,
+
+    cap_state: text_input::State,
+    cap_data: String,
+    cap_error: String,
+
+    floor_state: text_input::State,
+    floor_data: String,
+    floor_error: String,
+
+    max_change_state: text_input::State,
+    max_change_data: String,
+    max_change_error: String,
+}
+
+/// The unit the loan duration is entered in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum DurationUnit {
+    #[default]
+    Years,
+    Terms,
+}
+
+/// Selectable weekday of day-based terms with a human readable name,
+/// where none keeps the weekday of the first due date.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct WeekdayOption(Option<Weekday>);
+
+impl fmt::Display for WeekdayOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self.0 {
+            None => "First due date",
+            Some(Weekday::Mon) => "Monday",
+            Some(Weekday::Tue) => "Tuesday",
+            Some(Weekday::Wed) => "Wednesday",
+            Some(Weekday::Thu) => "Thursday",
+            Some(Weekday::Fri) => "Friday",
+            Some(Weekday::Sat) => "Saturday",
+            Some(Weekday::Sun) => "Sunday",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+/// Selectable month with a human readable name.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MonthOption(Month);
+
+impl Default for MonthOption {
+    fn default() -> Self {
+        MonthOption(Month::January)
+    }
+}
+
+impl fmt::Display for MonthOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.name())
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -31,15 +153,107 @@ pub enum WidgetMessage {
     InterestChanged(String),
     AdministrationFeeChanged(String),
     InstallmentFeeChanged(String),
+    DurationUnitSelected(DurationUnit),
+    DurationChanged(String),
+    TermsPerYearSelected(TermsPerYear),
+    DueWithinMonthSelected(MonthlyDueDate),
+    FirstInstallmentMonthSelected(MonthOption),
+    DueWeekdaySelected(WeekdayOption),
+    BusinessDayConventionSelected(BusinessDayConvention),
+    BalloonChanged(String),
+    InterestOnlyTermsChanged(String),
+    AmortisationSelected(Amortisation),
+    ExtraPolicySelected(ExtraInstallmentPolicy),
+    FixedUntilChanged(String),
+    FloatingRatesChanged(String),
+    MarginChanged(String),
+    IndexResetSelected(RecurringInterval),
+    LookbackChanged(String),
+    CapChanged(String),
+    FloorChanged(String),
+    MaxChangeChanged(String),
 }
 
 impl EventInitialization {
-    pub fn new(event: &LoanInitialization) -> EventInitialization {
+    pub fn new(date: NaiveDate, event: &LoanInitialization) -> EventInitialization {
+        // The duration is presented in years when it adds up to a whole number of years.
+        let terms_per_year = event.terms_per_year.to_u32();
+        let (duration_unit, duration_data) = if event.terms % terms_per_year == 0 {
+            (
+                DurationUnit::Years,
+                (event.terms / terms_per_year).to_string(),
+            )
+        } else {
+            (DurationUnit::Terms, event.terms.to_string())
+        };
+
+        // All days of the month, where the named ones are listed first.
+        let due_within_month_options = [
+            MonthlyDueDate::First,
+            MonthlyDueDate::Mid,
+            MonthlyDueDate::End,
+        ]
+        .iter()
+        .copied()
+        .chain((2..=30).filter(|d| *d != 15).map(MonthlyDueDate::Date))
+        .collect();
+
+        let first_installment_month_options = (1..=12)
+            .filter_map(Month::from_u32)
+            .map(MonthOption)
+            .collect();
+
+        let due_weekday_options = std::iter::once(None)
+            .chain((0..7).filter_map(Weekday::from_u32).map(Some))
+            .map(WeekdayOption)
+            .collect();
+
+        // A margin is only present when the floating rates are a reference index.
+        let (floating_rates_data, margin_data, index_reset, lookback_days) =
+            match &event.floating_rate {
+                Some(FloatingRate::Path(rates)) => (
+                    format_dated_rates(rates),
+                    String::new(),
+                    RecurringInterval::Monthly,
+                    0,
+                ),
+                Some(FloatingRate::Indexed(indexed)) => (
+                    format_dated_rates(indexed.index.rates()),
+                    indexed.margin.to_string(),
+                    indexed.reset,
+                    indexed.lookback_days,
+                ),
+                None => (String::new(), String::new(), RecurringInterval::Monthly, 0),
+            };
+
         Self {
+            disbursement_date_data: date.to_string(),
             loan_data: event.loan.to_string(),
             interest_data: event.nominal_interest.to_string(),
             administration_fee_data: event.administration_fee.to_string(),
             installment_fee_data: event.installment_fee.to_string(),
+            duration_unit,
+            duration_data,
+            terms_per_year: event.terms_per_year,
+            due_within_month: named_due_date(event.due_within_month),
+            due_within_month_options,
+            first_installment_month: MonthOption(event.first_installment_month),
+            first_installment_month_options,
+            due_weekday: WeekdayOption(event.due_weekday),
+            due_weekday_options,
+            business_day_convention: event.business_day_convention,
+            balloon_data: event.balloon.map_or(String::new(), |b| b.to_string()),
+            interest_only_terms_data: event.interest_only_terms.to_string(),
+            amortisation: event.amortisation,
+            extra_policy: event.extra_policy,
+            fixed_until_data: event.fixed_until.map_or(String::new(), |d| d.to_string()),
+            floating_rates_data,
+            margin_data,
+            index_reset,
+            lookback_data: lookback_days.to_string(),
+            cap_data: format_optional(event.rate_limits.cap),
+            floor_data: format_optional(event.rate_limits.floor),
+            max_change_data: format_optional(event.rate_limits.max_change),
             ..Default::default()
         }
     }
@@ -53,129 +267,590 @@ impl EventInitialization {
         match message {
             WidgetMessage::DisbursementDateChanged(data) => {
                 self.disbursement_date_data = data;
-                // TODO: Validate the error and report to the user
-                if let Ok(date) = NaiveDate::from_str(&self.disbursement_date_data) {
-                    calc.change_event_date(event_index, date);
-                }
+                self.disbursement_date_error =
+                    match NaiveDate::from_str(&self.disbursement_date_data) {
+                        Ok(date) => match calc.change_event_date(event_index, date) {
+                            Ok(()) => String::new(),
+                            Err(e) => e,
+                        },
+                        Err(_) => "expecting a date formatted as yyyy-mm-dd".to_string(),
+                    };
             }
             WidgetMessage::LoanChanged(data) => {
                 self.loan_data = data;
-                let (_, event) = calc.event_index(event_index);
-                match event {
-                    LoanEvent::Initial(init) => {
-                        if let Ok(loan) = f64::from_str(&self.loan_data) {
-                            init.loan = loan;
-                        }
+                self.loan_error = match parse_positive(&self.loan_data) {
+                    Ok(loan) => {
+                        initial_mut(calc, event_index).loan = loan;
+                        String::new()
                     }
-                    _ => panic!("resolved to unexpected event"),
-                }
+                    Err(e) => e,
+                };
             }
             WidgetMessage::InterestChanged(data) => {
                 self.interest_data = data;
-                let (_, event) = calc.event_index(event_index);
-                match event {
-                    LoanEvent::Initial(init) => {
-                        // Expecting interest to lie within range 0.0 to 100.0
-                        // Anything outside 100.0 interest would be ludicrous.
-                        if let Ok(interest) = f64::from_str(&self.interest_data) {
-                            init.nominal_interest = interest;
-                        }
+                // Expecting interest to lie within range 0.0 to 100.0
+                // Anything outside 100.0 interest would be ludicrous.
+                self.interest_error = match parse_positive(&self.interest_data) {
+                    Ok(interest) if interest > 100.0 => {
+                        "expecting an interest of at most 100".to_string()
                     }
-                    _ => panic!("resolved to unexpected event"),
-                }
+                    Ok(interest) => {
+                        initial_mut(calc, event_index).nominal_interest = interest;
+                        String::new()
+                    }
+                    Err(e) => e,
+                };
             }
             WidgetMessage::AdministrationFeeChanged(data) => {
                 self.administration_fee_data = data;
-                let (_, event) = calc.event_index(event_index);
-                match event {
-                    LoanEvent::Initial(init) => {
-                        // Expecting interest to lie within range 0.0 to 100.0
-                        // Anything outside 100.0 interest would be ludicrous.
-                        if let Ok(fee) = f64::from_str(&self.administration_fee_data) {
-                            init.administration_fee = fee;
-                        }
+                self.administration_fee_error = match parse_fee(&self.administration_fee_data) {
+                    Ok(fee) => {
+                        initial_mut(calc, event_index).administration_fee = fee;
+                        String::new()
                     }
-                    _ => panic!("resolved to unexpected event"),
-                }
+                    Err(e) => e,
+                };
             }
             WidgetMessage::InstallmentFeeChanged(data) => {
                 self.installment_fee_data = data;
-                let (_, event) = calc.event_index(event_index);
-                match event {
-                    LoanEvent::Initial(init) => {
-                        // Expecting interest to lie within range 0.0 to 100.0
-                        // Anything outside 100.0 interest would be ludicrous.
-                        if let Ok(fee) = f64::from_str(&self.installment_fee_data) {
-                            init.installment_fee = fee;
-                        }
+                self.installment_fee_error = match parse_fee(&self.installment_fee_data) {
+                    Ok(fee) => {
+                        initial_mut(calc, event_index).installment_fee = fee;
+                        String::new()
+                    }
+                    Err(e) => e,
+                };
+            }
+            WidgetMessage::DurationUnitSelected(unit) => {
+                // Present the current duration in the newly selected unit.
+                let init = initial(calc, event_index);
+                let terms_per_year = init.terms_per_year.to_u32();
+                self.duration_data = match unit {
+                    DurationUnit::Years if init.terms % terms_per_year == 0 => {
+                        (init.terms / terms_per_year).to_string()
+                    }
+                    DurationUnit::Years => (init.terms as f64 / terms_per_year as f64).to_string(),
+                    DurationUnit::Terms => init.terms.to_string(),
+                };
+                self.duration_unit = unit;
+                self.update_terms(calc, event_index);
+            }
+            WidgetMessage::DurationChanged(data) => {
+                self.duration_data = data;
+                self.update_terms(calc, event_index);
+            }
+            WidgetMessage::TermsPerYearSelected(terms_per_year) => {
+                self.terms_per_year = terms_per_year;
+                initial_mut(calc, event_index).terms_per_year = terms_per_year;
+                // A duration in years spans a different number of terms.
+                self.update_terms(calc, event_index);
+            }
+            WidgetMessage::DueWithinMonthSelected(due) => {
+                self.due_within_month = due;
+                initial_mut(calc, event_index).due_within_month = due;
+            }
+            WidgetMessage::FirstInstallmentMonthSelected(month) => {
+                self.first_installment_month = month;
+                initial_mut(calc, event_index).first_installment_month = month.0;
+            }
+            WidgetMessage::DueWeekdaySelected(weekday) => {
+                self.due_weekday = weekday;
+                initial_mut(calc, event_index).due_weekday = weekday.0;
+            }
+            WidgetMessage::BusinessDayConventionSelected(convention) => {
+                self.business_day_convention = convention;
+                initial_mut(calc, event_index).business_day_convention = convention;
+            }
+            WidgetMessage::BalloonChanged(data) => {
+                self.balloon_data = data;
+                self.balloon_error = match parse_balloon(&self.balloon_data) {
+                    Ok(balloon) => {
+                        initial_mut(calc, event_index).balloon = balloon;
+                        String::new()
                     }
-                    _ => panic!("resolved to unexpected event"),
-                }
+                    Err(e) => e,
+                };
+            }
+            WidgetMessage::InterestOnlyTermsChanged(data) => {
+                self.interest_only_terms_data = data;
+                self.interest_only_terms_error = match parse_terms(&self.interest_only_terms_data) {
+                    Ok(terms) => {
+                        initial_mut(calc, event_index).interest_only_terms = terms;
+                        String::new()
+                    }
+                    Err(e) => e,
+                };
+            }
+            WidgetMessage::AmortisationSelected(amortisation) => {
+                self.amortisation = amortisation;
+                initial_mut(calc, event_index).amortisation = amortisation;
+            }
+            WidgetMessage::ExtraPolicySelected(policy) => {
+                self.extra_policy = policy;
+                initial_mut(calc, event_index).extra_policy = policy;
+            }
+            WidgetMessage::FixedUntilChanged(data) => {
+                self.fixed_until_data = data;
+                self.update_floating_rate(calc, event_index);
+            }
+            WidgetMessage::FloatingRatesChanged(data) => {
+                self.floating_rates_data = data;
+                self.update_floating_rate(calc, event_index);
+            }
+            WidgetMessage::MarginChanged(data) => {
+                self.margin_data = data;
+                self.update_floating_rate(calc, event_index);
+            }
+            WidgetMessage::IndexResetSelected(reset) => {
+                self.index_reset = reset;
+                self.update_floating_rate(calc, event_index);
+            }
+            WidgetMessage::LookbackChanged(data) => {
+                self.lookback_data = data;
+                self.update_floating_rate(calc, event_index);
+            }
+            WidgetMessage::CapChanged(data) => {
+                self.cap_data = data;
+                self.update_rate_limits(calc, event_index);
             }
+            WidgetMessage::FloorChanged(data) => {
+                self.floor_data = data;
+                self.update_rate_limits(calc, event_index);
+            }
+            WidgetMessage::MaxChangeChanged(data) => {
+                self.max_change_data = data;
+                self.update_rate_limits(calc, event_index);
+            }
+        }
+    }
+
+    /// Apply the fixed-rate period and the floating rate on the loan,
+    /// once every related field is valid.
+    fn update_floating_rate(&mut self, calc: &mut InteractiveCalculator, event_index: usize) {
+        let fixed_until = match self.fixed_until_data.trim() {
+            "" => Ok(None),
+            data => NaiveDate::from_str(data)
+                .map(Some)
+                .map_err(|_| "expecting a date formatted as yyyy-mm-dd".to_string()),
+        };
+        let rates = parse_dated_rates(&self.floating_rates_data);
+        let margin = match self.margin_data.trim() {
+            "" => Ok(None),
+            data => f64::from_str(data)
+                .map(Some)
+                .map_err(|_| "expecting a number".to_string()),
+        };
+        let lookback_days = u32::from_str(self.lookback_data.trim())
+            .map_err(|_| "expecting a whole number of days".to_string());
+
+        self.fixed_until_error = fixed_until.clone().err().unwrap_or_default();
+        self.floating_rates_error = rates.clone().err().unwrap_or_default();
+        self.margin_error = margin.clone().err().unwrap_or_default();
+        self.lookback_error = lookback_days.clone().err().unwrap_or_default();
+
+        if let (Ok(fixed_until), Ok(rates), Ok(margin), Ok(lookback_days)) =
+            (fixed_until, rates, margin, lookback_days)
+        {
+            let reset = self.index_reset;
+            let init = initial_mut(calc, event_index);
+            init.fixed_until = fixed_until;
+            init.floating_rate = match margin {
+                _ if rates.is_empty() => None,
+                Some(margin) => Some(FloatingRate::Indexed(IndexedRate {
+                    index: IndexSeries::new(rates),
+                    margin,
+                    reset,
+                    lookback_days,
+                })),
+                None => Some(FloatingRate::Path(rates)),
+            };
         }
     }
 
+    /// Apply the limits on the nominal interest, once every limit is valid.
+    fn update_rate_limits(&mut self, calc: &mut InteractiveCalculator, event_index: usize) {
+        let cap = parse_optional_positive(&self.cap_data);
+        let floor = parse_optional_positive(&self.floor_data);
+        let max_change = parse_optional_positive(&self.max_change_data);
+
+        self.cap_error = cap.clone().err().unwrap_or_default();
+        self.floor_error = floor.clone().err().unwrap_or_default();
+        self.max_change_error = max_change.clone().err().unwrap_or_default();
+
+        if let (Ok(cap), Ok(floor), Ok(max_change)) = (cap, floor, max_change) {
+            let rate_limits = RateLimits {
+                cap,
+                floor,
+                max_change,
+            };
+            match rate_limits.validate() {
+                Ok(()) => initial_mut(calc, event_index).rate_limits = rate_limits,
+                Err(e) => self.floor_error = e,
+            }
+        }
+    }
+
+    /// Apply the entered duration as the number of terms on the loan.
+    fn update_terms(&mut self, calc: &mut InteractiveCalculator, event_index: usize) {
+        let terms = match self.duration_unit {
+            DurationUnit::Years => f64::from_str(&self.duration_data)
+                .ok()
+                .map(|years| years * self.terms_per_year.to_u32() as f64)
+                .filter(|terms| terms.fract() == 0.0)
+                .map(|terms| terms as i64),
+            DurationUnit::Terms => i64::from_str(&self.duration_data).ok(),
+        };
+
+        self.duration_error = match terms {
+            Some(terms) if terms > 0 && terms <= u32::MAX as i64 => {
+                initial_mut(calc, event_index).terms = terms as u32;
+                String::new()
+            }
+            _ if self.duration_unit == DurationUnit::Years => {
+                "expecting a number of years adding up to whole terms".to_string()
+            }
+            _ => "expecting a positive whole number of terms".to_string(),
+        };
+    }
+
     pub fn view(&mut self) -> Element<WidgetMessage> {
+        let duration_label = match self.duration_unit {
+            DurationUnit::Years => "Years:",
+            DurationUnit::Terms => "Terms:",
+        };
+
         Column::new()
             // Render the top title
             .push(Text::new("Loan start").size(30))
             // Render a single row within the column
+            .push(input_row(
+                "Disbursement date:",
+                &mut self.disbursement_date_state,
+                "yyyy-mm-dd",
+                &self.disbursement_date_data,
+                &self.disbursement_date_error,
+                WidgetMessage::DisbursementDateChanged,
+            ))
+            .push(input_row(
+                "Loan:",
+                &mut self.loan_state,
+                "",
+                &self.loan_data,
+                &self.loan_error,
+                WidgetMessage::LoanChanged,
+            ))
+            .push(input_row(
+                "Interest:",
+                &mut self.interest_state,
+                "",
+                &self.interest_data,
+                &self.interest_error,
+                WidgetMessage::InterestChanged,
+            ))
+            .push(input_row(
+                "Administration fee:",
+                &mut self.administration_fee_state,
+                "",
+                &self.administration_fee_data,
+                &self.administration_fee_error,
+                WidgetMessage::AdministrationFeeChanged,
+            ))
+            .push(input_row(
+                "Installment fee:",
+                &mut self.installment_fee_state,
+                "",
+                &self.installment_fee_data,
+                &self.installment_fee_error,
+                WidgetMessage::InstallmentFeeChanged,
+            ))
+            .push(
+                Row::new()
+                    .spacing(10)
+                    .push(Text::new("Duration in:"))
+                    .push(Space::with_width(Length::Fill))
+                    .push(Radio::new(
+                        DurationUnit::Years,
+                        "Years",
+                        Some(self.duration_unit),
+                        WidgetMessage::DurationUnitSelected,
+                    ))
+                    .push(Radio::new(
+                        DurationUnit::Terms,
+                        "Terms",
+                        Some(self.duration_unit),
+                        WidgetMessage::DurationUnitSelected,
+                    )),
+            )
+            .push(input_row(
+                duration_label,
+                &mut self.duration_state,
+                "",
+                &self.duration_data,
+                &self.duration_error,
+                WidgetMessage::DurationChanged,
+            ))
             .push(
                 Row::new()
-                    .push(Text::new("Disbursement date:"))
+                    .push(Text::new("Terms per year:"))
                     .push(Space::with_width(Length::Fill))
-                    .push(TextInput::new(
-                        &mut self.disbursement_date_state,
-                        "yyyy-mm-dd",
-                        &self.disbursement_date_data,
-                        WidgetMessage::DisbursementDateChanged,
+                    .push(self.terms_per_year_selection.view(
+                        &TermsPerYear::ALL,
+                        self.terms_per_year,
+                        WidgetMessage::TermsPerYearSelected,
                     )),
             )
             .push(
                 Row::new()
-                    .push(Text::new("Loan:"))
+                    .push(Text::new("Due within month:"))
                     .push(Space::with_width(Length::Fill))
-                    .push(TextInput::new(
-                        &mut self.loan_state,
-                        "",
-                        &self.loan_data,
-                        WidgetMessage::LoanChanged,
+                    .push(self.due_within_month_selection.view(
+                        &self.due_within_month_options,
+                        self.due_within_month,
+                        WidgetMessage::DueWithinMonthSelected,
                     )),
             )
             .push(
                 Row::new()
-                    .push(Text::new("Interest:"))
+                    .push(Text::new("First installment month:"))
                     .push(Space::with_width(Length::Fill))
-                    .push(TextInput::new(
-                        &mut self.interest_state,
-                        "",
-                        &self.interest_data,
-                        WidgetMessage::InterestChanged,
+                    .push(self.first_installment_month_selection.view(
+                        &self.first_installment_month_options,
+                        self.first_installment_month,
+                        WidgetMessage::FirstInstallmentMonthSelected,
                     )),
             )
             .push(
                 Row::new()
-                    .push(Text::new("Administration fee:"))
+                    .push(Text::new("Weekly terms due on:"))
                     .push(Space::with_width(Length::Fill))
-                    .push(TextInput::new(
-                        &mut self.administration_fee_state,
-                        "",
-                        &self.administration_fee_data,
-                        WidgetMessage::AdministrationFeeChanged,
+                    .push(self.due_weekday_selection.view(
+                        &self.due_weekday_options,
+                        self.due_weekday,
+                        WidgetMessage::DueWeekdaySelected,
                     )),
             )
+            .push(radio_row(
+                "Business days:",
+                &BusinessDayConvention::ALL,
+                self.business_day_convention,
+                WidgetMessage::BusinessDayConventionSelected,
+            ))
+            .push(input_row(
+                "Balloon:",
+                &mut self.balloon_state,
+                "amount or percentage",
+                &self.balloon_data,
+                &self.balloon_error,
+                WidgetMessage::BalloonChanged,
+            ))
+            .push(input_row(
+                "Interest-only terms:",
+                &mut self.interest_only_terms_state,
+                "0",
+                &self.interest_only_terms_data,
+                &self.interest_only_terms_error,
+                WidgetMessage::InterestOnlyTermsChanged,
+            ))
+            .push(radio_row(
+                "Amortisation:",
+                &Amortisation::ALL,
+                self.amortisation,
+                WidgetMessage::AmortisationSelected,
+            ))
+            .push(radio_row(
+                "Extra payments:",
+                &ExtraInstallmentPolicy::ALL,
+                self.extra_policy,
+                WidgetMessage::ExtraPolicySelected,
+            ))
+            .push(input_row(
+                "Fixed rate until:",
+                &mut self.fixed_until_state,
+                "yyyy-mm-dd",
+                &self.fixed_until_data,
+                &self.fixed_until_error,
+                WidgetMessage::FixedUntilChanged,
+            ))
+            .push(input_row(
+                "Floating rates:",
+                &mut self.floating_rates_state,
+                "yyyy-mm-dd=rate, ...",
+                &self.floating_rates_data,
+                &self.floating_rates_error,
+                WidgetMessage::FloatingRatesChanged,
+            ))
+            .push(input_row(
+                "Margin over floating rates:",
+                &mut self.margin_state,
+                "none",
+                &self.margin_data,
+                &self.margin_error,
+                WidgetMessage::MarginChanged,
+            ))
             .push(
                 Row::new()
-                    .push(Text::new("Installment fee:"))
+                    .push(Text::new("Index reset:"))
                     .push(Space::with_width(Length::Fill))
-                    .push(TextInput::new(
-                        &mut self.installment_fee_state,
-                        "",
-                        &self.installment_fee_data,
-                        WidgetMessage::InstallmentFeeChanged,
+                    .push(self.index_reset_selection.view(
+                        &RecurringInterval::ALL,
+                        self.index_reset,
+                        WidgetMessage::IndexResetSelected,
                     )),
             )
+            .push(input_row(
+                "Index lookback days:",
+                &mut self.lookback_state,
+                "0",
+                &self.lookback_data,
+                &self.lookback_error,
+                WidgetMessage::LookbackChanged,
+            ))
+            .push(input_row(
+                "Interest cap:",
+                &mut self.cap_state,
+                "none",
+                &self.cap_data,
+                &self.cap_error,
+                WidgetMessage::CapChanged,
+            ))
+            .push(input_row(
+                "Interest floor:",
+                &mut self.floor_state,
+                "none",
+                &self.floor_data,
+                &self.floor_error,
+                WidgetMessage::FloorChanged,
+            ))
+            .push(input_row(
+                "Max change per reset:",
+                &mut self.max_change_state,
+                "none",
+                &self.max_change_data,
+                &self.max_change_error,
+                WidgetMessage::MaxChangeChanged,
+            ))
             // Finalize
             .into()
     }
 }
+
+/// Render a labeled text input, with any validation error next to it.
+fn input_row<'a>(
+    label: &str,
+    state: &'a mut text_input::State,
+    placeholder: &str,
+    data: &str,
+    error: &str,
+    on_change: fn(String) -> WidgetMessage,
+) -> Row<'a, WidgetMessage> {
+    Row::new()
+        .spacing(10)
+        .push(Text::new(label))
+        .push(Space::with_width(Length::Fill))
+        .push(Text::new(error).color(Color::from_rgb(0.76, 0.094, 0.027)))
+        .push(TextInput::new(state, placeholder, data, on_change).width(Length::Units(200)))
+}
+
+/// Render a labeled set of radio buttons, one for each of the options.
+fn radio_row<'a, T>(
+    label: &str,
+    options: &[T],
+    selected: T,
+    on_select: fn(T) -> WidgetMessage,
+) -> Row<'a, WidgetMessage>
+where
+    T: Copy + Eq + fmt::Display + 'static,
+{
+    options.iter().fold(
+        Row::new()
+            .spacing(10)
+            .push(Text::new(label))
+            .push(Space::with_width(Length::Fill)),
+        |row, option| {
+            row.push(Radio::new(
+                *option,
+                option.to_string(),
+                Some(selected),
+                on_select,
+            ))
+        },
+    )
+}
+
+/// Resolve the initial loan event on the calculator, only to read it.
+fn initial(calc: &InteractiveCalculator, event_index: usize) -> &LoanInitialization {
+    match calc.event(event_index).1 {
+        LoanEvent::Initial(init) => init,
+        _ => panic!("resolved to unexpected event"),
+    }
+}
+
+/// Resolve the initial loan event on the calculator, to change it.
+fn initial_mut(calc: &mut InteractiveCalculator, event_index: usize) -> &mut LoanInitialization {
+    let (_, event) = calc.event_index(event_index);
+    match event {
+        LoanEvent::Initial(init) => init,
+        _ => panic!("resolved to unexpected event"),
+    }
+}
+
+fn parse_positive(data: &str) -> Result<f64, String> {
+    match f64::from_str(data) {
+        Ok(value) if value > 0.0 => Ok(value),
+        Ok(_) => Err("expecting a positive number".to_string()),
+        Err(_) => Err("expecting a number".to_string()),
+    }
+}
+
+fn parse_fee(data: &str) -> Result<f64, String> {
+    match f64::from_str(data) {
+        Ok(value) if value >= 0.0 => Ok(value),
+        Ok(_) => Err("expecting a fee of zero or more".to_string()),
+        Err(_) => Err("expecting a number".to_string()),
+    }
+}
+
+/// Parse an optional positive number, where an empty input is none.
+fn parse_optional_positive(data: &str) -> Result<Option<f64>, String> {
+    match data.trim() {
+        "" => Ok(None),
+        data => parse_positive(data).map(Some),
+    }
+}
+
+fn format_optional(value: Option<f64>) -> String {
+    value.map_or(String::new(), |v| v.to_string())
+}
+
+/// The option of the due date pick list, where the days of the named
+/// due dates are presented by their name.
+fn named_due_date(due: MonthlyDueDate) -> MonthlyDueDate {
+    match due {
+        MonthlyDueDate::Date(1) => MonthlyDueDate::First,
+        MonthlyDueDate::Date(15) => MonthlyDueDate::Mid,
+        MonthlyDueDate::Date(d) if d >= 31 => MonthlyDueDate::End,
+        due => due,
+    }
+}
+
+fn parse_terms(data: &str) -> Result<u32, String> {
+    u32::from_str(data).map_err(|_| "expecting a whole number of terms".to_string())
+}
+
+/// Parse a balloon as either an amount, or a percentage when suffixed by `%`.
+/// An empty balloon is no balloon.
+fn parse_balloon(data: &str) -> Result<Option<Balloon>, String> {
+    if data.trim().is_empty() {
+        return Ok(None);
+    }
+
+    match Balloon::from_str(data)? {
+        Balloon::Percentage(value) if !(0.0..100.0).contains(&value) => {
+            Err("expecting a percentage below 100".to_string())
+        }
+        Balloon::Amount(value) if value < 0.0 => {
+            Err("expecting a balloon of zero or more".to_string())
+        }
+        balloon => Ok(Some(balloon)),
+    }
+}
@@ -0,0 +1,608 @@
+//! Represent the widget listing all events on the loan, with forms to add and edit them.
+
+use crate::{
+    ExtraInstallmentPolicy, InteractiveCalculator, LoanEvent, LoanExtraInstallment,
+    LoanInterestChange, LoanPayoff, LoanRecurringExtraInstallments, LoanRefinance,
+    LoanRepaymentFreeze, LoanTransfer, PrepaymentFee, RecurringInterval,
+};
+
+use chrono::{Duration, NaiveDate};
+use iced::{
+    button, text_input, Button, Color, Column, Element, Length, Radio, Row, Space, Text, TextInput,
+};
+use std::num::NonZeroU32;
+use std::str::FromStr;
+
+/// The timeline lists every event on the calculator in date order.
+/// A single event can be edited at a time, through the editor of its kind.
+#[derive(Default)]
+pub struct EventTimeline {
+    add_interest_change_state: button::State,
+    add_extra_single_state: button::State,
+    add_extra_recurring_state: button::State,
+    add_repayment_freeze_state: button::State,
+    add_refinance_state: button::State,
+    add_bank_transfer_state: button::State,
+    add_early_payoff_state: button::State,
+
+    rows: Vec<TimelineRow>,
+    editor: Option<EventEditor>,
+
+    error: String,
+}
+
+/// A single rendered event in the timeline.
+#[derive(Default)]
+struct TimelineRow {
+    date: String,
+    description: String,
+    /// The initial loan event is edited through its own widget.
+    editable: bool,
+
+    edit_state: button::State,
+    remove_state: button::State,
+}
+
+/// The kinds of events that can be added to the timeline.
+#[derive(Clone, Copy, Debug)]
+pub enum NewEvent {
+    InterestChange,
+    ExtraSingle,
+    ExtraRecurring,
+    RepaymentFreeze,
+    Refinance,
+    BankTransfer,
+    EarlyPayoff,
+}
+
+#[derive(Clone, Debug)]
+pub enum WidgetMessage {
+    Add(NewEvent),
+    Edit(usize),
+    Remove(usize),
+    Editor(EditorMessage),
+    CloseEditor,
+}
+
+#[derive(Clone, Debug)]
+pub enum EditorMessage {
+    DateChanged(String),
+    FieldChanged(usize, String),
+    IntervalSelected(RecurringInterval),
+    PolicySelected(Option<ExtraInstallmentPolicy>),
+}
+
+impl EventTimeline {
+    pub fn new(calc: &InteractiveCalculator) -> EventTimeline {
+        let mut timeline = Self::default();
+        timeline.sync(calc);
+        timeline
+    }
+
+    /// Rebuild the rendered rows from the events on the calculator.
+    pub fn sync(&mut self, calc: &InteractiveCalculator) {
+        self.rows = calc
+            .events()
+            .map(|(date, event)| TimelineRow {
+                date: date.to_string(),
+                description: describe(event),
+                editable: !matches!(event, LoanEvent::Initial(_)),
+                ..Default::default()
+            })
+            .collect();
+    }
+
+    pub fn update(&mut self, calc: &mut InteractiveCalculator, message: WidgetMessage) {
+        self.error.clear();
+
+        match message {
+            WidgetMessage::Add(kind) => match add_event(calc, kind) {
+                Ok(index) => {
+                    let (date, event) = calc.event(index);
+                    self.editor = Some(EventEditor::new(index, date, event));
+                }
+                Err(e) => self.error = e,
+            },
+            WidgetMessage::Edit(index) => {
+                let (date, event) = calc.event(index);
+                self.editor = Some(EventEditor::new(index, date, event));
+            }
+            WidgetMessage::Remove(index) => {
+                if let Err(e) = calc.remove_event(index) {
+                    self.error = e;
+                } else if let Some(editor) = self.editor.as_mut() {
+                    if editor.index == index {
+                        self.editor = None;
+                    } else if editor.index > index {
+                        editor.index -= 1;
+                    }
+                }
+            }
+            WidgetMessage::Editor(m) => {
+                if let Some(editor) = self.editor.as_mut() {
+                    editor.update(calc, m);
+                }
+            }
+            WidgetMessage::CloseEditor => self.editor = None,
+        }
+
+        self.sync(calc);
+    }
+
+    pub fn view(&mut self) -> Element<WidgetMessage> {
+        let add_buttons = Row::new()
+            .spacing(5)
+            .push(
+                Button::new(
+                    &mut self.add_interest_change_state,
+                    Text::new("Interest change"),
+                )
+                .on_press(WidgetMessage::Add(NewEvent::InterestChange)),
+            )
+            .push(
+                Button::new(&mut self.add_extra_single_state, Text::new("Extra payment"))
+                    .on_press(WidgetMessage::Add(NewEvent::ExtraSingle)),
+            )
+            .push(
+                Button::new(
+                    &mut self.add_extra_recurring_state,
+                    Text::new("Recurring extra"),
+                )
+                .on_press(WidgetMessage::Add(NewEvent::ExtraRecurring)),
+            )
+            .push(
+                Button::new(&mut self.add_repayment_freeze_state, Text::new("Freeze"))
+                    .on_press(WidgetMessage::Add(NewEvent::RepaymentFreeze)),
+            )
+            .push(
+                Button::new(&mut self.add_refinance_state, Text::new("Refinance"))
+                    .on_press(WidgetMessage::Add(NewEvent::Refinance)),
+            )
+            .push(
+                Button::new(
+                    &mut self.add_bank_transfer_state,
+                    Text::new("Bank transfer"),
+                )
+                .on_press(WidgetMessage::Add(NewEvent::BankTransfer)),
+            )
+            .push(
+                Button::new(&mut self.add_early_payoff_state, Text::new("Early payoff"))
+                    .on_press(WidgetMessage::Add(NewEvent::EarlyPayoff)),
+            );
+
+        let rows = self.rows.iter_mut().enumerate().fold(
+            Column::new().spacing(5),
+            |column, (index, row)| {
+                let mut r = Row::new()
+                    .spacing(10)
+                    .push(Text::new(&row.date).width(Length::Units(100)))
+                    .push(Text::new(&row.description))
+                    .push(Space::with_width(Length::Fill));
+                if row.editable {
+                    r = r
+                        .push(
+                            Button::new(&mut row.edit_state, Text::new("Edit"))
+                                .on_press(WidgetMessage::Edit(index)),
+                        )
+                        .push(
+                            Button::new(&mut row.remove_state, Text::new("Remove"))
+                                .on_press(WidgetMessage::Remove(index)),
+                        );
+                }
+                column.push(r)
+            },
+        );
+
+        let mut content = Column::new()
+            .spacing(10)
+            // Render the top title
+            .push(Text::new("Events").size(30))
+            .push(Text::new(&self.error).color(Color::from_rgb(0.76, 0.094, 0.027)))
+            .push(add_buttons)
+            .push(rows);
+
+        if let Some(editor) = self.editor.as_mut() {
+            content = content.push(editor.view());
+        }
+
+        // Finalize
+        content.into()
+    }
+}
+
+/// Add a new event of the provided kind to the calculator, with a set of sensible defaults.
+/// The new event is placed a month after the latest event on the loan.
+///
+/// Returns the index of the added event.
+fn add_event(calc: &mut InteractiveCalculator, kind: NewEvent) -> Result<usize, String> {
+    // SAFETY(unwrap): the calculator always contains the initial event.
+    let (latest, _) = calc.events().last().unwrap();
+    let date = latest + Duration::days(30);
+
+    let nominal_interest = calc.event(0).1.initial().nominal_interest;
+
+    match kind {
+        NewEvent::InterestChange => {
+            calc.add_event_interest_change(date, LoanInterestChange { nominal_interest })?
+        }
+        NewEvent::ExtraSingle => calc.add_event_extra_single(
+            date,
+            LoanExtraInstallment {
+                amount: 10000.0,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )?,
+        NewEvent::ExtraRecurring => calc.add_event_extra_recurring(
+            date,
+            LoanRecurringExtraInstallments {
+                amount: 1000.0,
+                // SAFETY(unwrap): constant non-zero value.
+                count: NonZeroU32::new(12).unwrap(),
+                recurring_interval: RecurringInterval::Monthly,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )?,
+        NewEvent::RepaymentFreeze => calc.add_event_repayment_freeze(
+            date,
+            LoanRepaymentFreeze {
+                // SAFETY(unwrap): constant non-zero value.
+                count: NonZeroU32::new(1).unwrap(),
+            },
+        )?,
+        NewEvent::Refinance => calc.add_event_refinance(
+            date,
+            LoanRefinance {
+                loan_increase: 100000.0,
+                administration_fee: 0.0,
+            },
+        )?,
+        NewEvent::BankTransfer => calc.add_event_bank_transfer(
+            date,
+            LoanTransfer {
+                administration_fee: 0.0,
+                prepayment_fee: None,
+            },
+        )?,
+        NewEvent::EarlyPayoff => calc.add_event_early_payoff(date, LoanPayoff::default())?,
+    }
+
+    // The added event is always the last event on its date.
+    Ok(calc.events().filter(|(d, _)| *d <= date).count() - 1)
+}
+
+/// A short, human readable description of an event.
+fn describe(event: &LoanEvent) -> String {
+    match event {
+        LoanEvent::Initial(init) => format!(
+            "Loan start: {:.2} at {} %",
+            init.loan, init.nominal_interest
+        ),
+        LoanEvent::InterestChange(change) => {
+            format!("Interest change to {} %", change.nominal_interest)
+        }
+        LoanEvent::BankTransfer(transfer) => format!(
+            "Bank transfer, administration fee {:.2}",
+            transfer.administration_fee
+        ),
+        LoanEvent::Refinance(refinance) => format!(
+            "Refinance of {:.2}, administration fee {:.2}",
+            refinance.loan_increase, refinance.administration_fee
+        ),
+        LoanEvent::Extra(extra) if extra.count.get() == 1 => {
+            format!("Extra payment of {:.2}", extra.amount)
+        }
+        LoanEvent::Extra(extra) => format!(
+            "{} extra payments of {:.2}, {}",
+            extra.count, extra.amount, extra.recurring_interval
+        ),
+        LoanEvent::RepaymentFreeze(freeze) => {
+            format!("Repayment freeze for {} installments", freeze.count)
+        }
+        LoanEvent::EarlyPayoff(payoff) => format!(
+            "Early payoff, administration fee {:.2}",
+            payoff.administration_fee
+        ),
+    }
+}
+
+/// The editing form of a single event.
+/// Each kind of event has its own set of fields.
+struct EventEditor {
+    /// The index of the edited event on the calculator.
+    index: usize,
+    title: &'static str,
+
+    date_state: text_input::State,
+    date_data: String,
+
+    fields: Vec<EditorField>,
+    /// The selected interval, only present for extra installments.
+    interval: Option<RecurringInterval>,
+    /// The selected extra installment policy, where `None` follows the loan.
+    /// Only present for extra installments.
+    policy: Option<Option<ExtraInstallmentPolicy>>,
+
+    close_state: button::State,
+    error: String,
+}
+
+struct EditorField {
+    label: &'static str,
+    state: text_input::State,
+    data: String,
+}
+
+impl EditorField {
+    fn new(label: &'static str, data: String) -> Self {
+        Self {
+            label,
+            state: text_input::State::default(),
+            data,
+        }
+    }
+}
+
+impl EventEditor {
+    fn new(index: usize, date: NaiveDate, event: &LoanEvent) -> Self {
+        let mut interval = None;
+        let mut policy = None;
+        let (title, fields) = match event {
+            LoanEvent::Initial(_) => ("Loan start", Vec::new()),
+            LoanEvent::InterestChange(change) => (
+                "Interest change",
+                vec![EditorField::new(
+                    "Interest:",
+                    change.nominal_interest.to_string(),
+                )],
+            ),
+            LoanEvent::BankTransfer(transfer) => (
+                "Bank transfer",
+                vec![
+                    EditorField::new(
+                        "Administration fee:",
+                        transfer.administration_fee.to_string(),
+                    ),
+                    EditorField::new(
+                        "Prepayment fee:",
+                        transfer
+                            .prepayment_fee
+                            .map_or(String::new(), |f| f.to_string()),
+                    ),
+                ],
+            ),
+            LoanEvent::Refinance(refinance) => (
+                "Refinance",
+                vec![
+                    EditorField::new("Loan increase:", refinance.loan_increase.to_string()),
+                    EditorField::new(
+                        "Administration fee:",
+                        refinance.administration_fee.to_string(),
+                    ),
+                ],
+            ),
+            LoanEvent::Extra(extra) => {
+                interval = Some(extra.recurring_interval);
+                policy = Some(extra.policy);
+                (
+                    "Extra payment",
+                    vec![
+                        EditorField::new("Amount:", extra.amount.to_string()),
+                        EditorField::new("Count:", extra.count.to_string()),
+                        EditorField::new(
+                            "Prepayment fee:",
+                            extra
+                                .prepayment_fee
+                                .map_or(String::new(), |f| f.to_string()),
+                        ),
+                    ],
+                )
+            }
+            LoanEvent::RepaymentFreeze(freeze) => (
+                "Repayment freeze",
+                vec![EditorField::new("Installments:", freeze.count.to_string())],
+            ),
+            LoanEvent::EarlyPayoff(payoff) => (
+                "Early payoff",
+                vec![
+                    EditorField::new("Administration fee:", payoff.administration_fee.to_string()),
+                    EditorField::new(
+                        "Prepayment fee:",
+                        payoff
+                            .prepayment_fee
+                            .map_or(String::new(), |f| f.to_string()),
+                    ),
+                ],
+            ),
+        };
+
+        Self {
+            index,
+            title,
+            date_state: text_input::State::default(),
+            date_data: date.to_string(),
+            fields,
+            interval,
+            policy,
+            close_state: button::State::default(),
+            error: String::new(),
+        }
+    }
+
+    fn update(&mut self, calc: &mut InteractiveCalculator, message: EditorMessage) {
+        self.error.clear();
+
+        match message {
+            EditorMessage::DateChanged(data) => {
+                self.date_data = data;
+                match NaiveDate::from_str(&self.date_data) {
+                    Ok(date) => match calc.change_event_date(self.index, date) {
+                        // The moved event is always the last event on its new date.
+                        Ok(()) => {
+                            self.index = calc.events().filter(|(d, _)| *d <= date).count() - 1
+                        }
+                        Err(e) => self.error = e,
+                    },
+                    Err(_) => self.error = "expecting a date formatted as yyyy-mm-dd".to_string(),
+                }
+            }
+            EditorMessage::FieldChanged(field, data) => {
+                self.fields[field].data = data;
+                if let Err(e) = self.apply(calc.event_index(self.index).1) {
+                    self.error = e;
+                }
+            }
+            EditorMessage::IntervalSelected(interval) => {
+                self.interval = Some(interval);
+                if let Err(e) = self.apply(calc.event_index(self.index).1) {
+                    self.error = e;
+                }
+            }
+            EditorMessage::PolicySelected(policy) => {
+                self.policy = Some(policy);
+                if let Err(e) = self.apply(calc.event_index(self.index).1) {
+                    self.error = e;
+                }
+            }
+        }
+    }
+
+    /// Parse all fields of the form, and apply them to the event if they are all valid.
+    fn apply(&self, event: &mut LoanEvent) -> Result<(), String> {
+        match event {
+            LoanEvent::Initial(_) => {}
+            LoanEvent::InterestChange(change) => {
+                let parsed = LoanInterestChange {
+                    nominal_interest: self.parse_f64(0)?,
+                };
+                parsed.validate()?;
+                *change = parsed;
+            }
+            LoanEvent::BankTransfer(transfer) => {
+                let administration_fee = self.parse_f64(0)?;
+                transfer.prepayment_fee = self.parse_prepayment_fee(1)?;
+                transfer.administration_fee = administration_fee;
+            }
+            LoanEvent::Refinance(refinance) => {
+                let loan_increase = self.parse_f64(0)?;
+                refinance.administration_fee = self.parse_f64(1)?;
+                refinance.loan_increase = loan_increase;
+            }
+            LoanEvent::Extra(extra) => {
+                let amount = self.parse_f64(0)?;
+                let count = self.parse_count(1)?;
+                extra.prepayment_fee = self.parse_prepayment_fee(2)?;
+                extra.count = count;
+                extra.amount = amount;
+                if let Some(interval) = self.interval {
+                    extra.recurring_interval = interval;
+                }
+                if let Some(policy) = self.policy {
+                    extra.policy = policy;
+                }
+            }
+            LoanEvent::RepaymentFreeze(freeze) => {
+                freeze.count = self.parse_count(0)?;
+            }
+            LoanEvent::EarlyPayoff(payoff) => {
+                let administration_fee = self.parse_f64(0)?;
+                payoff.prepayment_fee = self.parse_prepayment_fee(1)?;
+                payoff.administration_fee = administration_fee;
+            }
+        }
+        Ok(())
+    }
+
+    fn parse_f64(&self, field: usize) -> Result<f64, String> {
+        let field = &self.fields[field];
+        f64::from_str(&field.data).map_err(|_| format!("{} expecting a number", field.label))
+    }
+
+    fn parse_count(&self, field: usize) -> Result<NonZeroU32, String> {
+        let field = &self.fields[field];
+        NonZeroU32::from_str(&field.data)
+            .map_err(|_| format!("{} expecting a positive whole number", field.label))
+    }
+
+    /// Parse an optional prepayment fee, where an empty field is no fee.
+    fn parse_prepayment_fee(&self, field: usize) -> Result<Option<PrepaymentFee>, String> {
+        let field = &self.fields[field];
+        if field.data.trim().is_empty() {
+            return Ok(None);
+        }
+        PrepaymentFee::from_str(&field.data)
+            .map(Some)
+            .map_err(|e| format!("{} {}", field.label, e))
+    }
+
+    fn view(&mut self) -> Element<WidgetMessage> {
+        let mut form = Column::new()
+            .spacing(5)
+            .push(
+                Row::new()
+                    .push(Text::new(self.title).size(24))
+                    .push(Space::with_width(Length::Fill))
+                    .push(
+                        Button::new(&mut self.close_state, Text::new("Close"))
+                            .on_press(WidgetMessage::CloseEditor),
+                    ),
+            )
+            .push(Text::new(&self.error).color(Color::from_rgb(0.76, 0.094, 0.027)))
+            .push(
+                Row::new()
+                    .push(Text::new("Date:"))
+                    .push(Space::with_width(Length::Fill))
+                    .push(TextInput::new(
+                        &mut self.date_state,
+                        "yyyy-mm-dd",
+                        &self.date_data,
+                        |data| WidgetMessage::Editor(EditorMessage::DateChanged(data)),
+                    )),
+            );
+
+        for (index, field) in self.fields.iter_mut().enumerate() {
+            form = form.push(
+                Row::new()
+                    .push(Text::new(field.label))
+                    .push(Space::with_width(Length::Fill))
+                    .push(TextInput::new(
+                        &mut field.state,
+                        "",
+                        &field.data,
+                        move |data| WidgetMessage::Editor(EditorMessage::FieldChanged(index, data)),
+                    )),
+            );
+        }
+
+        if let Some(selected) = self.interval {
+            form = RecurringInterval::ALL.iter().fold(
+                form.push(Text::new("Interval:")),
+                |form, interval| {
+                    form.push(Radio::new(
+                        *interval,
+                        interval.to_string(),
+                        Some(selected),
+                        |i| WidgetMessage::Editor(EditorMessage::IntervalSelected(i)),
+                    ))
+                },
+            );
+        }
+
+        if let Some(selected) = self.policy {
+            let policies = ExtraInstallmentPolicy::ALL.iter().copied().map(Some);
+            form = std::iter::once(None).chain(policies).fold(
+                form.push(Text::new("Extra payments:")),
+                |form, policy| {
+                    let label = policy.map_or("as the loan".to_string(), |p| p.to_string());
+                    form.push(Radio::new(policy, label, Some(selected), |p| {
+                        WidgetMessage::Editor(EditorMessage::PolicySelected(p))
+                    }))
+                },
+            );
+        }
+
+        // Finalize
+        form.into()
+    }
+}
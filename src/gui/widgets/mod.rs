@@ -1,4 +1,13 @@
 //! Encapsulation of various widgets or collection of widgets used together
 
+#[cfg(not(target_arch = "wasm32"))]
+pub mod balance_chart;
+#[cfg(target_arch = "wasm32")]
+pub mod balance_chart_web;
+pub mod comparison;
 pub mod event_initialization;
+pub mod event_timeline;
+pub mod scenarios;
+pub mod schedule;
+pub mod selection;
 pub mod summary;
@@ -0,0 +1,258 @@
+//! A widget to save, switch between, export and import loan scenarios.
+
+use crate::gui::storage::{self, Store};
+use crate::{InteractiveCalculator, Scenario};
+
+use iced::{
+    button, text_input, Button, Color, Column, Element, Length, Row, Space, Text, TextInput,
+};
+
+/// The name of a scenario before the user names it.
+const UNNAMED: &str = "Unnamed";
+
+/// The scenarios widget keeps the persisted store of scenarios in sync with the calculator.
+///
+/// The scenario being edited is persisted on every change, and restored on the next start.
+/// Saved scenarios are listed by name, and loading one replaces the events of the calculator.
+pub struct Scenarios {
+    store: Store,
+
+    name: String,
+    name_state: text_input::State,
+    save_state: button::State,
+
+    rows: Vec<ScenarioRow>,
+
+    file: String,
+    file_state: text_input::State,
+    export_state: button::State,
+    import_state: button::State,
+
+    error: String,
+}
+
+/// A single saved scenario in the list.
+#[derive(Default)]
+struct ScenarioRow {
+    load_state: button::State,
+    delete_state: button::State,
+}
+
+#[derive(Clone, Debug)]
+pub enum WidgetMessage {
+    NameChanged(String),
+    Save,
+    Load(usize),
+    Delete(usize),
+    FileChanged(String),
+    Export,
+    Import,
+}
+
+impl Scenarios {
+    /// Read the persisted scenarios.
+    /// Failing to read them is reported in the widget, starting with an empty store.
+    pub fn new() -> Scenarios {
+        let (store, error) = match Store::load() {
+            Ok(store) => (store, String::new()),
+            Err(e) => (Store::default(), e),
+        };
+        let name = store
+            .current
+            .as_ref()
+            .map(|s| s.name.clone())
+            .unwrap_or_else(|| UNNAMED.to_string());
+
+        let mut scenarios = Scenarios {
+            store,
+            name,
+            name_state: text_input::State::default(),
+            save_state: button::State::default(),
+            rows: Vec::new(),
+            file: String::new(),
+            file_state: text_input::State::default(),
+            export_state: button::State::default(),
+            import_state: button::State::default(),
+            error,
+        };
+        scenarios.sync();
+        scenarios
+    }
+
+    /// The calculator of the scenario that was edited in the last session, if any.
+    pub fn restore(&mut self) -> Option<InteractiveCalculator> {
+        let restored = self.store.current.as_ref()?.to_calculator();
+        match restored {
+            Ok(calculator) => Some(calculator),
+            Err(e) => {
+                self.error = e;
+                None
+            }
+        }
+    }
+
+    /// Persist the current state of the calculator as the scenario being edited.
+    pub fn autosave(&mut self, calc: &InteractiveCalculator) {
+        self.store.current = Some(Scenario::from_calculator(&self.name, calc));
+        self.persist();
+    }
+
+    /// Returns true if the events of the calculator were replaced.
+    pub fn update(&mut self, calc: &mut InteractiveCalculator, message: WidgetMessage) -> bool {
+        self.error.clear();
+
+        match message {
+            WidgetMessage::NameChanged(name) => self.name = name,
+            WidgetMessage::Save => {
+                if self.name.trim().is_empty() {
+                    self.error = "a scenario must have a name".to_string();
+                    return false;
+                }
+
+                let scenario = Scenario::from_calculator(self.name.trim(), calc);
+                match self
+                    .store
+                    .saved
+                    .iter_mut()
+                    .find(|s| s.name == scenario.name)
+                {
+                    Some(existing) => *existing = scenario,
+                    None => self.store.saved.push(scenario),
+                }
+                self.persist();
+            }
+            WidgetMessage::Load(index) => {
+                let scenario = &self.store.saved[index];
+                match scenario.to_calculator() {
+                    Ok(loaded) => {
+                        self.name = scenario.name.clone();
+                        *calc = loaded;
+                        return true;
+                    }
+                    Err(e) => self.error = e,
+                }
+            }
+            WidgetMessage::Delete(index) => {
+                self.store.saved.remove(index);
+                self.persist();
+            }
+            WidgetMessage::FileChanged(file) => self.file = file,
+            WidgetMessage::Export => {
+                let scenario = Scenario::from_calculator(&self.name, calc);
+                if let Err(e) = storage::export(&scenario, &self.file) {
+                    self.error = e;
+                }
+            }
+            WidgetMessage::Import => match storage::import(&self.file).and_then(|s| {
+                let loaded = s.to_calculator()?;
+                Ok((s.name, loaded))
+            }) {
+                Ok((name, loaded)) => {
+                    self.name = name;
+                    *calc = loaded;
+                    return true;
+                }
+                Err(e) => self.error = e,
+            },
+        }
+
+        false
+    }
+
+    pub fn view(&mut self) -> Element<WidgetMessage> {
+        let save = Row::new()
+            .spacing(10)
+            .push(
+                TextInput::new(
+                    &mut self.name_state,
+                    "Scenario name",
+                    &self.name,
+                    WidgetMessage::NameChanged,
+                )
+                .padding(5),
+            )
+            .push(
+                Button::new(&mut self.save_state, Text::new("Save")).on_press(WidgetMessage::Save),
+            );
+
+        let rows = self
+            .store
+            .saved
+            .iter()
+            .zip(self.rows.iter_mut())
+            .enumerate()
+            .fold(
+                Column::new().spacing(5),
+                |column, (index, (scenario, row))| {
+                    column.push(
+                        Row::new()
+                            .spacing(10)
+                            .push(Text::new(&scenario.name))
+                            .push(Space::with_width(Length::Fill))
+                            .push(
+                                Button::new(&mut row.load_state, Text::new("Load"))
+                                    .on_press(WidgetMessage::Load(index)),
+                            )
+                            .push(
+                                Button::new(&mut row.delete_state, Text::new("Delete"))
+                                    .on_press(WidgetMessage::Delete(index)),
+                            ),
+                    )
+                },
+            );
+
+        // Native builds read and write files, while the web downloads or pastes the content.
+        let placeholder = if cfg!(target_arch = "wasm32") {
+            "File name to export, or scenario content to import"
+        } else {
+            "Path of the scenario file"
+        };
+        let file = Row::new()
+            .spacing(10)
+            .push(
+                TextInput::new(
+                    &mut self.file_state,
+                    placeholder,
+                    &self.file,
+                    WidgetMessage::FileChanged,
+                )
+                .padding(5),
+            )
+            .push(
+                Button::new(&mut self.export_state, Text::new("Export"))
+                    .on_press(WidgetMessage::Export),
+            )
+            .push(
+                Button::new(&mut self.import_state, Text::new("Import"))
+                    .on_press(WidgetMessage::Import),
+            );
+
+        Column::new()
+            .spacing(10)
+            // Render the top title
+            .push(Text::new("Scenarios").size(30))
+            .push(Text::new(&self.error).color(Color::from_rgb(0.76, 0.094, 0.027)))
+            .push(save)
+            .push(rows)
+            .push(file)
+            // Finalize
+            .into()
+    }
+
+    fn persist(&mut self) {
+        if let Err(e) = self.store.persist() {
+            self.error = e;
+        }
+        self.sync();
+    }
+
+    /// Rebuild the rendered rows from the saved scenarios.
+    fn sync(&mut self) {
+        self.rows = self
+            .store
+            .saved
+            .iter()
+            .map(|_| ScenarioRow::default())
+            .collect();
+    }
+}
@@ -0,0 +1,251 @@
+//! A schedule widget listing every installment over the lifetime of the loan.
+
+use crate::gui::Message;
+use crate::reports::{Daily, ScheduleEntry, YearlyResult};
+
+use chrono::Datelike;
+use iced::{
+    button, scrollable, Button, Checkbox, Color, Column, Element, HorizontalAlignment, Length, Row,
+    Scrollable, Text,
+};
+
+/// Rows where an event takes effect are highlighted in this color.
+const EVENT_COLOR: Color = Color::from_rgb(0.0, 0.35, 0.75);
+/// Rows of interest-only installments are dimmed in this color.
+const INTEREST_ONLY_COLOR: Color = Color::from_rgb(0.45, 0.45, 0.45);
+
+/// This schedule widget lays out and renders the table of installments.
+/// Only rendering of text shall be provided. Manipulation of entries is not done through
+/// this rendering widget.
+///
+/// The rows may be grouped by year, where each year has a subtotal row
+/// that collapses or expands the installments of that year.
+#[derive(Default)]
+pub struct Schedule {
+    entries: Vec<ScheduleEntry>,
+    years: Vec<YearGroup>,
+    group_by_year: bool,
+
+    scroll: scrollable::State,
+    error: String,
+}
+
+/// The subtotal of a single year in the schedule.
+struct YearGroup {
+    subtotal: YearlyResult,
+    expanded: bool,
+    toggle_state: button::State,
+}
+
+#[derive(Clone, Debug)]
+pub enum WidgetMessage {
+    GroupByYear(bool),
+    ToggleYear(i32),
+}
+
+impl Schedule {
+    pub fn update(&mut self, ledger: Result<Vec<Daily>, String>) {
+        match ledger {
+            Ok(ledger) => {
+                // Keep the collapsed state of the years that remain in the schedule.
+                let expanded: Vec<i32> = self
+                    .years
+                    .iter()
+                    .filter(|y| y.expanded)
+                    .map(|y| y.subtotal.year)
+                    .collect();
+
+                self.entries = ScheduleEntry::from_ledger(&ledger);
+                self.years = YearlyResult::from_ledger(&ledger)
+                    .into_iter()
+                    .map(|subtotal| YearGroup {
+                        expanded: expanded.contains(&subtotal.year),
+                        subtotal,
+                        toggle_state: button::State::default(),
+                    })
+                    .collect();
+                self.error.clear();
+            }
+            Err(e) => {
+                self.entries.clear();
+                self.years.clear();
+                self.error = e;
+            }
+        }
+    }
+
+    pub fn on_message(&mut self, message: WidgetMessage) {
+        match message {
+            WidgetMessage::GroupByYear(group) => self.group_by_year = group,
+            WidgetMessage::ToggleYear(year) => {
+                if let Some(y) = self.years.iter_mut().find(|y| y.subtotal.year == year) {
+                    y.expanded = !y.expanded;
+                }
+            }
+        }
+    }
+
+    pub fn view(&mut self) -> Element<Message> {
+        let mut table = Scrollable::new(&mut self.scroll)
+            .height(Length::Fill)
+            .spacing(2)
+            .push(header_row());
+
+        if self.group_by_year {
+            for year in self.years.iter_mut() {
+                let subtotal = &year.subtotal;
+                let label = format!(
+                    "{} {}",
+                    if year.expanded { "-" } else { "+" },
+                    subtotal.year
+                );
+                table = table.push(
+                    Button::new(
+                        &mut year.toggle_state,
+                        amounts_row(
+                            label,
+                            [
+                                subtotal.total_payed(),
+                                subtotal.interest,
+                                subtotal.principal,
+                                subtotal.fee,
+                                subtotal.extra,
+                                subtotal.closing_outstanding_loan,
+                            ],
+                            None,
+                        ),
+                    )
+                    .width(Length::Fill)
+                    .on_press(Message::Schedule(WidgetMessage::ToggleYear(subtotal.year))),
+                );
+
+                if year.expanded {
+                    for entry in self
+                        .entries
+                        .iter()
+                        .filter(|e| e.date.year() == subtotal.year)
+                    {
+                        table = table.push(entry_row(entry));
+                    }
+                }
+            }
+        } else {
+            for entry in self.entries.iter() {
+                table = table.push(entry_row(entry));
+            }
+        }
+
+        Column::new()
+            .spacing(10)
+            // Render the top title
+            .push(Text::new("Schedule").size(40))
+            .push(Text::new(&self.error).color(Color::from_rgb(0.76, 0.094, 0.027)))
+            .push(Checkbox::new(
+                self.group_by_year,
+                "Group by year",
+                |group| Message::Schedule(WidgetMessage::GroupByYear(group)),
+            ))
+            .push(table)
+            // Finalize
+            .into()
+    }
+}
+
+fn header_row<'a>() -> Row<'a, Message> {
+    [
+        "Date",
+        "Payment",
+        "Interest",
+        "Principal",
+        "Fee",
+        "Extra",
+        "Balance",
+    ]
+    .iter()
+    .fold(Row::new().spacing(10), |row, title| {
+        row.push(
+            Text::new(*title)
+                .width(Length::Units(100))
+                .horizontal_alignment(HorizontalAlignment::Right),
+        )
+    })
+}
+
+/// Render a single entry, marking the end of a fixed-rate period, any limit on the
+/// nominal interest and an early payoff above its amounts.
+fn entry_row<'a>(entry: &ScheduleEntry) -> Column<'a, Message> {
+    let mut column = Column::new();
+    if let Some(interest) = entry.fixed_rate_end() {
+        column = column.push(
+            Text::new(format!(
+                "The fixed rate ends, the loan continues at a floating {} %",
+                interest
+            ))
+            .color(EVENT_COLOR),
+        );
+    }
+    if let Some((limit, requested)) = entry.rate_limited() {
+        column = column.push(
+            Text::new(format!(
+                "The interest {} applies, rather than {} %",
+                limit, requested
+            ))
+            .color(EVENT_COLOR),
+        );
+    }
+
+    if let Some(quote) = entry.early_payoff() {
+        column = column.push(
+            Text::new(format!(
+                "The loan is payed off with {:.2}, of which {:.2} in fees",
+                quote.total,
+                quote.administration_fee + quote.prepayment_fee
+            ))
+            .color(EVENT_COLOR),
+        );
+    }
+
+    column.push(amounts_row_of(entry))
+}
+
+fn amounts_row_of<'a>(entry: &ScheduleEntry) -> Row<'a, Message> {
+    let color = if entry.is_event() {
+        Some(EVENT_COLOR)
+    } else if entry.is_interest_only() {
+        Some(INTEREST_ONLY_COLOR)
+    } else {
+        None
+    };
+
+    amounts_row(
+        entry.date.to_string(),
+        [
+            entry.payment,
+            entry.interest,
+            entry.principal,
+            entry.fee,
+            entry.extra,
+            entry.outstanding_loan.max(0.0),
+        ],
+        color,
+    )
+}
+
+/// Render a single row of the table, with a leading label followed by the amounts.
+fn amounts_row<'a>(label: String, amounts: [f64; 6], color: Option<Color>) -> Row<'a, Message> {
+    let text = |content: String| {
+        let text = Text::new(content)
+            .width(Length::Units(100))
+            .horizontal_alignment(HorizontalAlignment::Right);
+        match color {
+            Some(c) => text.color(c),
+            None => text,
+        }
+    };
+
+    amounts
+        .iter()
+        .fold(Row::new().spacing(10).push(text(label)), |row, amount| {
+            row.push(text(format!("{:.2}", amount)))
+        })
+}
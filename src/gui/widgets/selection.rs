@@ -0,0 +1,63 @@
+//! A widget to select one value among a fixed set of options.
+//!
+//! Native builds render a pick list. Pick lists are not available on the web,
+//! where a set of radio buttons is rendered instead.
+
+use iced::Element;
+#[cfg(not(target_arch = "wasm32"))]
+use iced::{pick_list, PickList};
+#[cfg(target_arch = "wasm32")]
+use iced::{Radio, Row};
+
+pub struct Selection<T> {
+    #[cfg(not(target_arch = "wasm32"))]
+    state: pick_list::State<T>,
+    #[cfg(target_arch = "wasm32")]
+    _options: std::marker::PhantomData<T>,
+}
+
+impl<T> Default for Selection<T> {
+    fn default() -> Self {
+        Self {
+            #[cfg(not(target_arch = "wasm32"))]
+            state: pick_list::State::default(),
+            #[cfg(target_arch = "wasm32")]
+            _options: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<T> Selection<T>
+where
+    T: Copy + Eq + ToString + 'static,
+{
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn view<'a, Message: 'static>(
+        &'a mut self,
+        options: &'a [T],
+        selected: T,
+        on_selected: impl Fn(T) -> Message + 'static,
+    ) -> Element<'a, Message> {
+        PickList::new(&mut self.state, options, Some(selected), on_selected).into()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub fn view<'a, Message: 'static>(
+        &'a mut self,
+        options: &'a [T],
+        selected: T,
+        on_selected: impl Fn(T) -> Message + Copy + 'static,
+    ) -> Element<'a, Message> {
+        options
+            .iter()
+            .fold(Row::new().spacing(5), |row, option| {
+                row.push(Radio::new(
+                    *option,
+                    option.to_string(),
+                    Some(selected),
+                    on_selected,
+                ))
+            })
+            .into()
+    }
+}
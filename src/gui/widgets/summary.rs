@@ -1,7 +1,7 @@
 //! A summary widget for the overall state of the entire loan and downpayment.
 
 use crate::gui::Message;
-use crate::reports::TotalResult;
+use crate::InteractiveCalculator;
 
 use iced::{Color, Column, Element, HorizontalAlignment, Length, Row, Space, Text};
 
@@ -11,53 +11,97 @@ use iced::{Color, Column, Element, HorizontalAlignment, Length, Row, Space, Text
 ///
 /// The information included is:
 /// * Loan start date
+/// * First installment date
 /// * Loan finish date
 /// * Initial loan sum
+/// * Interest-only and repayment terms
+/// * Regular term payment, and after any fixed-rate period
 /// * Total sum
 /// * Loan cost (The cost of the loan over the period from start to finish)
+/// * Effective annual rate
+/// * Extra payments, and the interest saved by them
+/// * Remaining balance as of today
 ///
 /// Interest and fees are not included here, since they may/will be altered over the course of the
 /// loan, and should be retrieved from the timeline widget.
 #[derive(Default)]
 pub struct Summary {
     disbursement_date: String,
+    first_installment_date: String,
     finalized_date: String,
     // Formatted as: completed/planned
     terms: String,
+    interest_only_terms: String,
+    term_payment: String,
+    term_payment_after_fixed_rate: String,
     total_cost: String,
     total_loan: String,
     total_interest: String,
     total_fee: String,
+    total_prepayment_fee: String,
+    effective_annual_rate: String,
+    total_extra: String,
+    total_payoff: String,
+    interest_saved_by_extra: String,
+    // Formatted as: cap/floor/change limit
+    rate_limit_triggers: String,
+    interest_saved_by_rate_limits: String,
+    outstanding_today: String,
 
     error: String,
 }
 
 impl Summary {
-    pub fn update(&mut self, total: Result<TotalResult, String>) {
-        match total {
-            Ok(t) => {
-                self.disbursement_date = t.disbursement_date.to_string();
-                self.finalized_date = t.disbursement_date.to_string();
-                self.terms = format!("{}/{}", t.completed_terms, t.planned_terms);
-                self.total_cost = format!("{:.2}", t.total_cost);
-                self.total_loan = format!("{:.2}", t.total_loan);
-                self.total_interest = format!("{:.2}", t.total_interest);
-                self.total_fee = format!("{:.2}", t.total_fee);
+    pub fn update(&mut self, calculator: &InteractiveCalculator) {
+        if let Err(e) = self.compute(calculator) {
+            *self = Summary::default();
+            self.error = e;
+        }
+    }
 
-                self.error.clear();
-            }
-            Err(e) => {
-                self.disbursement_date = String::new();
-                self.finalized_date = String::new();
-                self.terms = String::new();
-                self.total_cost = String::new();
-                self.total_loan = String::new();
-                self.total_interest = String::new();
-                self.total_fee = String::new();
+    /// Compute and format every figure of the summary.
+    fn compute(&mut self, calculator: &InteractiveCalculator) -> Result<(), String> {
+        let t = calculator.compute()?;
+        self.disbursement_date = t.disbursement_date.to_string();
+        self.first_installment_date = t.first_installment_date.to_string();
+        self.finalized_date = t.end_date.to_string();
+        self.terms = format!("{}/{}", t.completed_terms, t.planned_terms);
+        self.interest_only_terms = t.interest_only_terms.to_string();
+        self.term_payment = format!("{:.2}", t.term_payment);
+        self.term_payment_after_fixed_rate = t
+            .term_payment_after_fixed_rate
+            .map_or("-".to_string(), |p| format!("{:.2}", p));
+        self.total_cost = format!("{:.2}", t.total_cost);
+        self.total_loan = format!("{:.2}", t.total_loan);
+        self.total_interest = format!("{:.2}", t.total_interest);
+        self.total_fee = format!("{:.2}", t.total_fee);
+        self.total_prepayment_fee = format!("{:.2}", t.total_prepayment_fee);
+        self.effective_annual_rate = t
+            .effective_annual_rate
+            .map_or("-".to_string(), |r| format!("{:.3} %", r));
+        self.total_extra = format!("{:.2}", t.total_extra_installment);
+        self.total_payoff = format!("{:.2}", t.total_payoff_installment);
+        // The savings calculate the loan once more, only for loans with extra installments
+        // or limits on the nominal interest.
+        self.interest_saved_by_extra = format!("{:.2}", calculator.interest_saved_by_extra()?);
+        self.rate_limit_triggers = format!(
+            "{}/{}/{}",
+            t.rate_cap_triggers, t.rate_floor_triggers, t.rate_change_limit_triggers
+        );
+        self.interest_saved_by_rate_limits =
+            format!("{:.2}", calculator.interest_saved_by_rate_limits()?);
 
-                self.error = e;
-            }
-        }
+        // Nothing is outstanding ahead of the disbursement.
+        let today = chrono::Utc::today().naive_utc();
+        let outstanding = if today < t.disbursement_date {
+            0.0
+        } else {
+            calculator.snapshot(today)?.outstanding_loan.max(0.0)
+        };
+        self.outstanding_today = format!("{:.2}", outstanding);
+
+        self.error.clear();
+        Ok(())
     }
 
     pub fn view(&mut self) -> Element<Message> {
@@ -65,67 +109,46 @@ impl Summary {
             // Render the top title
             .push(Text::new("Summary").size(40))
             .push(Text::new(&self.error).color(Color::from_rgb(0.76, 0.094, 0.027)))
-            // Render a single row within the column
-            .push(
-                Row::new()
-                    .push(Text::new("Disbursement date:"))
-                    .push(Space::with_width(Length::Fill))
-                    .push(
-                        Text::new(&self.disbursement_date)
-                            .horizontal_alignment(HorizontalAlignment::Right),
-                    ),
-            )
-            .push(
-                Row::new()
-                    .push(Text::new("Finalized date:"))
-                    .push(Space::with_width(Length::Fill))
-                    .push(
-                        Text::new(&self.finalized_date)
-                            .horizontal_alignment(HorizontalAlignment::Right),
-                    ),
-            )
-            .push(
-                Row::new()
-                    .push(Text::new("Terms (completed/planned):"))
-                    .push(Space::with_width(Length::Fill))
-                    .push(Text::new(&self.terms).horizontal_alignment(HorizontalAlignment::Right)),
-            )
-            .push(
-                Row::new()
-                    .push(Text::new("Principal loan:"))
-                    .push(Space::with_width(Length::Fill))
-                    .push(
-                        Text::new(&self.total_loan)
-                            .horizontal_alignment(HorizontalAlignment::Right),
-                    ),
-            )
-            .push(
-                Row::new()
-                    .push(Text::new("Cost:"))
-                    .push(Space::with_width(Length::Fill))
-                    .push(
-                        Text::new(&self.total_cost)
-                            .horizontal_alignment(HorizontalAlignment::Right),
-                    ),
-            )
-            .push(
-                Row::new()
-                    .push(Text::new("Interest:"))
-                    .push(Space::with_width(Length::Fill))
-                    .push(
-                        Text::new(&self.total_interest)
-                            .horizontal_alignment(HorizontalAlignment::Right),
-                    ),
-            )
-            .push(
-                Row::new()
-                    .push(Text::new("Fee:"))
-                    .push(Space::with_width(Length::Fill))
-                    .push(
-                        Text::new(&self.total_fee).horizontal_alignment(HorizontalAlignment::Right),
-                    ),
-            )
+            .push(row("Disbursement date:", &self.disbursement_date))
+            .push(row("First installment date:", &self.first_installment_date))
+            .push(row("Finalized date:", &self.finalized_date))
+            .push(row("Interest-only terms:", &self.interest_only_terms))
+            .push(row("Repayment terms (completed/planned):", &self.terms))
+            .push(row("Term payment:", &self.term_payment))
+            .push(row(
+                "Term payment after fixed rate:",
+                &self.term_payment_after_fixed_rate,
+            ))
+            .push(row("Principal loan:", &self.total_loan))
+            .push(row("Cost:", &self.total_cost))
+            .push(row("Interest:", &self.total_interest))
+            .push(row("Fee:", &self.total_fee))
+            .push(row("Of which prepayment fees:", &self.total_prepayment_fee))
+            .push(row("Effective rate:", &self.effective_annual_rate))
+            .push(row("Extra payments:", &self.total_extra))
+            .push(row("Early payoff:", &self.total_payoff))
+            .push(row(
+                "Interest saved by extra payments:",
+                &self.interest_saved_by_extra,
+            ))
+            .push(row(
+                "Interest limits triggered (cap/floor/change):",
+                &self.rate_limit_triggers,
+            ))
+            .push(row(
+                "Interest saved by interest limits:",
+                &self.interest_saved_by_rate_limits,
+            ))
+            .push(row("Remaining balance today:", &self.outstanding_today))
             // Finalize
             .into()
     }
 }
+
+/// Render a single row within the column, with the value aligned to the right.
+fn row<'a>(label: &str, value: &str) -> Row<'a, Message> {
+    Row::new()
+        .push(Text::new(label))
+        .push(Space::with_width(Length::Fill))
+        .push(Text::new(value).horizontal_alignment(HorizontalAlignment::Right))
+}
@@ -0,0 +1,81 @@
+//! Reference-rate indices, such as NIBOR, that floating loans are priced above.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A series of dated index rates, where each rate is effective from its date
+/// until the date of the next rate.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(from = "Vec<(NaiveDate, f64)>", into = "Vec<(NaiveDate, f64)>")]
+pub struct IndexSeries {
+    /// The rates, ordered by date.
+    rates: Vec<(NaiveDate, f64)>,
+}
+
+/// A single record of an index series file.
+#[derive(Deserialize)]
+struct IndexRecord {
+    date: NaiveDate,
+    rate: f64,
+}
+
+impl IndexSeries {
+    /// Construct a series from dated rates, in any order.
+    /// A later rate on the same date replaces an earlier one.
+    pub fn new(mut rates: Vec<(NaiveDate, f64)>) -> IndexSeries {
+        rates.reverse();
+        rates.sort_by_key(|(date, _)| *date);
+        rates.dedup_by_key(|(date, _)| *date);
+        IndexSeries { rates }
+    }
+
+    /// Read a series from CSV content with a `date,rate` header,
+    /// where dates are formatted as yyyy-mm-dd.
+    pub fn from_csv<R: std::io::Read>(reader: R) -> Result<IndexSeries, String> {
+        let rates = csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader)
+            .deserialize()
+            .map(|record| {
+                record
+                    .map(|r: IndexRecord| (r.date, r.rate))
+                    .map_err(|e| format!("invalid index series: {}", e))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+        Ok(IndexSeries::new(rates))
+    }
+
+    /// Read a series from a CSV file, see [`IndexSeries::from_csv`].
+    pub fn load(path: &Path) -> Result<IndexSeries, String> {
+        let file = std::fs::File::open(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        IndexSeries::from_csv(file)
+    }
+
+    /// The dated rates of the series, ordered by date.
+    pub fn rates(&self) -> &[(NaiveDate, f64)] {
+        &self.rates
+    }
+
+    /// The index rate effective on date, if the series has started by then.
+    pub fn rate_on(&self, date: NaiveDate) -> Option<f64> {
+        match self.rates.binary_search_by_key(&date, |(d, _)| *d) {
+            Ok(position) => Some(self.rates[position].1),
+            Err(0) => None,
+            Err(position) => Some(self.rates[position - 1].1),
+        }
+    }
+}
+
+impl From<Vec<(NaiveDate, f64)>> for IndexSeries {
+    fn from(rates: Vec<(NaiveDate, f64)>) -> Self {
+        IndexSeries::new(rates)
+    }
+}
+
+impl From<IndexSeries> for Vec<(NaiveDate, f64)> {
+    fn from(series: IndexSeries) -> Self {
+        series.rates
+    }
+}
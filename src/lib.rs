@@ -5,11 +5,25 @@
 //! the loan over its lifetime.
 
 mod calculator;
+mod calendar;
 mod events;
 #[cfg(feature = "gui")]
 pub mod gui;
+mod index;
+mod portfolio;
 mod reports;
+mod scenario;
 
-pub use calculator::{CompoundingStrategy, InteractiveCalculator};
+pub use calculator::{CompoundingStrategy, Engine, InteractiveCalculator};
+pub use calendar::{
+    easter_sunday, BusinessDayConvention, HolidayCalendar, NorwegianCalendar, WeekendCalendar,
+};
 pub use events::*;
-pub use reports::TotalResult;
+pub use index::IndexSeries;
+pub use portfolio::{Portfolio, PortfolioLoan, PortfolioResult};
+pub use reports::{
+    draw_balance, draw_yearly_breakdown, effective_annual_rate, Daily, LenderPeriod, LoanSnapshot,
+    MonthlyCashFlow, NotableEvents, PayoffQuote, ScheduleEntry, TaxStatement, TotalResult,
+    YearlyResult,
+};
+pub use scenario::{Scenario, ScenarioEvent};
@@ -0,0 +1,268 @@
+//! Computation of a portfolio of loans, aggregating the results of every loan.
+//!
+//! A portfolio is read from a JSON list of scenarios, or from a CSV file with
+//! a single loan on every line. With the `parallel` feature, the loans are
+//! computed on every available core.
+
+use crate::calculator::{future_month, InteractiveCalculator};
+use crate::calendar::BusinessDayConvention;
+use crate::events::{Amortisation, LoanInitialization, MonthlyDueDate, TermsPerYear};
+use crate::reports::{LoanSnapshot, TotalResult, YearlyResult};
+use crate::scenario::Scenario;
+
+use chrono::{Datelike, NaiveDate};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// A collection of named loans.
+#[derive(Clone, Default)]
+pub struct Portfolio {
+    loans: Vec<(String, InteractiveCalculator)>,
+}
+
+/// A single loan of a portfolio CSV file.
+///
+/// The first installment is due the month after the disbursement.
+#[derive(Deserialize)]
+struct PortfolioRecord {
+    name: String,
+    date: NaiveDate,
+    loan: f64,
+    nominal_interest: f64,
+    terms: u32,
+    /// Monthly terms, unless given.
+    terms_per_year: Option<u32>,
+    /// The 20th of the month, unless given.
+    due_day: Option<u32>,
+    installment_fee: Option<f64>,
+    administration_fee: Option<f64>,
+    /// Either `annuity` or `serial`, where annuity is the default.
+    amortisation: Option<String>,
+    business_day_convention: Option<String>,
+}
+
+impl PortfolioRecord {
+    fn to_calculator(&self) -> Result<InteractiveCalculator, String> {
+        let terms_per_year = match self.terms_per_year {
+            Some(terms) => *TermsPerYear::ALL
+                .iter()
+                .find(|t| t.to_u32() == terms)
+                .ok_or_else(|| format!("{}: unsupported terms per year {}", self.name, terms))?,
+            None => TermsPerYear::Twelve,
+        };
+        let amortisation = match self.amortisation.as_deref() {
+            Some(name) => *Amortisation::ALL
+                .iter()
+                .find(|a| a.to_string() == name.trim())
+                .ok_or_else(|| format!("{}: expecting either annuity or serial", self.name))?,
+            None => Amortisation::Annuity,
+        };
+        let business_day_convention = match self.business_day_convention.as_deref() {
+            Some(convention) => convention
+                .parse()
+                .map_err(|e| format!("{}: {}", self.name, e))?,
+            None => BusinessDayConvention::None,
+        };
+
+        let initial = LoanInitialization {
+            loan: self.loan,
+            nominal_interest: self.nominal_interest,
+            administration_fee: self.administration_fee.unwrap_or(0.0),
+            installment_fee: self.installment_fee.unwrap_or(0.0),
+            terms: self.terms,
+            terms_per_year,
+            due_within_month: MonthlyDueDate::Date(self.due_day.unwrap_or(20)),
+            first_installment_month: future_month(&self.date, 1),
+            business_day_convention,
+            amortisation,
+            ..LoanInitialization::default()
+        };
+        Ok(InteractiveCalculator::new(self.date, initial))
+    }
+}
+
+/// The results of every loan of a portfolio, along with the totals of the portfolio.
+#[derive(Debug)]
+pub struct PortfolioResult {
+    /// The date the outstanding loans and nominal interests are reported at.
+    pub as_of: NaiveDate,
+    /// The result of every loan, in the order of the portfolio.
+    pub loans: Vec<PortfolioLoan>,
+
+    /// The outstanding principal of every loan as of the date.
+    pub total_outstanding: f64,
+    /// The interest portion of installments payed within every year, over every loan.
+    pub interest_income: BTreeMap<i32, f64>,
+    /// The nominal interest of every loan as of the date, weighted by its outstanding principal.
+    /// This is zero when nothing is outstanding.
+    pub weighted_average_rate: f64,
+    /// The outstanding principal as of the date, by the year the loan is repayed in full.
+    pub maturity_profile: BTreeMap<i32, f64>,
+}
+
+/// The result of a single loan of a portfolio.
+#[derive(Debug)]
+pub struct PortfolioLoan {
+    pub name: String,
+    /// The totals over the lifetime of the loan.
+    pub summary: TotalResult,
+    /// The payments of every year of the loan.
+    pub yearly: Vec<YearlyResult>,
+    /// The state of the loan as of the date, unless it is disbursed at a later date.
+    pub snapshot: Option<LoanSnapshot>,
+}
+
+impl PortfolioLoan {
+    /// The outstanding principal as of the date of the portfolio result.
+    pub fn outstanding(&self) -> f64 {
+        self.snapshot.as_ref().map_or(0.0, |s| s.outstanding_loan)
+    }
+}
+
+impl Portfolio {
+    pub fn new() -> Portfolio {
+        Portfolio::default()
+    }
+
+    /// Add a named loan to the portfolio.
+    pub fn add(&mut self, name: &str, calculator: InteractiveCalculator) {
+        self.loans.push((name.to_string(), calculator));
+    }
+
+    /// Every named loan, in the order they were added.
+    pub fn loans(&self) -> impl Iterator<Item = (&str, &InteractiveCalculator)> {
+        self.loans.iter().map(|(name, c)| (name.as_str(), c))
+    }
+
+    pub fn len(&self) -> usize {
+        self.loans.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.loans.is_empty()
+    }
+
+    /// Construct a portfolio of the loans of scenarios, named by the scenarios.
+    pub fn from_scenarios(scenarios: &[Scenario]) -> Result<Portfolio, String> {
+        let mut portfolio = Portfolio::new();
+        for scenario in scenarios.iter() {
+            let calculator = scenario
+                .to_calculator()
+                .map_err(|e| format!("{}: {}", scenario.name, e))?;
+            portfolio.add(&scenario.name, calculator);
+        }
+        Ok(portfolio)
+    }
+
+    /// Read a portfolio from a JSON list of scenarios.
+    pub fn from_json(json: &str) -> Result<Portfolio, String> {
+        let scenarios: Vec<Scenario> =
+            serde_json::from_str(json).map_err(|e| format!("invalid portfolio: {}", e))?;
+        Portfolio::from_scenarios(&scenarios)
+    }
+
+    /// Read a portfolio from CSV content with a single loan on every line.
+    ///
+    /// The header names the columns `name`, `date`, `loan`, `nominal_interest` and `terms`,
+    /// along with the optional columns `terms_per_year`, `due_day`, `installment_fee`,
+    /// `administration_fee`, `amortisation` and `business_day_convention`.
+    /// Dates are formatted as yyyy-mm-dd.
+    pub fn from_csv<R: std::io::Read>(reader: R) -> Result<Portfolio, String> {
+        let mut portfolio = Portfolio::new();
+        for record in csv::ReaderBuilder::new()
+            .trim(csv::Trim::All)
+            .from_reader(reader)
+            .deserialize()
+        {
+            let record: PortfolioRecord =
+                record.map_err(|e| format!("invalid portfolio: {}", e))?;
+            portfolio.add(&record.name, record.to_calculator()?);
+        }
+        Ok(portfolio)
+    }
+
+    /// Read a portfolio from a CSV file when the path ends with `.csv`,
+    /// or from a JSON file otherwise.
+    pub fn load(path: &Path) -> Result<Portfolio, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => {
+                Portfolio::from_csv(content.as_bytes())
+            }
+            _ => Portfolio::from_json(&content),
+        }
+    }
+
+    /// Compute every loan of the portfolio, reporting the outstanding loans as of date.
+    ///
+    /// The first loan failing to compute fails the entire portfolio.
+    pub fn compute(&self, as_of: NaiveDate) -> Result<PortfolioResult, String> {
+        #[cfg(feature = "parallel")]
+        let loans = self.loans.par_iter();
+        #[cfg(not(feature = "parallel"))]
+        let loans = self.loans.iter();
+
+        let loans = loans
+            .map(|(name, calculator)| compute_loan(name, calculator, as_of))
+            .collect::<Result<Vec<_>, String>>()?;
+
+        let mut total_outstanding = 0.0;
+        let mut weighted_interest = 0.0;
+        let mut interest_income = BTreeMap::new();
+        let mut maturity_profile = BTreeMap::new();
+        for loan in loans.iter() {
+            for year in loan.yearly.iter() {
+                *interest_income.entry(year.year).or_insert(0.0) += year.interest;
+            }
+
+            let outstanding = loan.outstanding();
+            if let Some(snapshot) = loan.snapshot.as_ref().filter(|_| outstanding > 0.0) {
+                total_outstanding += outstanding;
+                weighted_interest += outstanding * snapshot.nominal_interest;
+                *maturity_profile
+                    .entry(loan.summary.end_date.year())
+                    .or_insert(0.0) += outstanding;
+            }
+        }
+
+        Ok(PortfolioResult {
+            as_of,
+            loans,
+            total_outstanding,
+            interest_income,
+            weighted_average_rate: if total_outstanding > 0.0 {
+                weighted_interest / total_outstanding
+            } else {
+                0.0
+            },
+            maturity_profile,
+        })
+    }
+}
+
+/// Compute the results of a single loan, along with its state as of date.
+fn compute_loan(
+    name: &str,
+    calculator: &InteractiveCalculator,
+    as_of: NaiveDate,
+) -> Result<PortfolioLoan, String> {
+    let failed = |e| format!("{}: {}", name, e);
+    let summary = calculator.compute().map_err(failed)?;
+    let yearly = YearlyResult::from_ledger(&calculator.ledger().map_err(failed)?);
+    let snapshot = if as_of < summary.disbursement_date {
+        None
+    } else {
+        Some(calculator.snapshot(as_of).map_err(failed)?)
+    };
+
+    Ok(PortfolioLoan {
+        name: name.to_string(),
+        summary,
+        yearly,
+        snapshot,
+    })
+}
@@ -1,6 +1,10 @@
 //! Models related to the reports of calculations
 
-use chrono::NaiveDate;
+use crate::events::{RateLimit, TermsPerYear};
+
+use chrono::{Datelike, NaiveDate};
+use plotters::coord::Shift;
+use plotters::prelude::*;
 
 /// This report includes the total computation of an installment loan.
 #[derive(Debug)]
@@ -15,10 +19,27 @@ pub struct TotalResult {
     pub total_repayment_installment: f64,
     /// The total sum of the disbursed loan that has been repayed in extra installments.
     pub total_extra_installment: f64,
+    /// The residual amount of the loan repayed with the last term, beyond the regular
+    /// term payment. This is part of the total repayment installments.
+    pub total_balloon_installment: f64,
     /// The total sum of interest payed on the loan over its duration.
     pub total_interest: f64,
     /// The total sum of fees associated with the loan repayment plan.
     pub total_fee: f64,
+    /// The portion of the total fees charged for prepaying the loan.
+    pub total_prepayment_fee: f64,
+    /// The outstanding loan repayed by an early payoff, ending the loan ahead of its schedule.
+    pub total_payoff_installment: f64,
+
+    /// The regular payment of the first scheduled repayment installment,
+    /// including the installment fee.
+    pub term_payment: f64,
+    /// The payment of the first scheduled repayment installment after the fixed-rate period,
+    /// including the installment fee. Only present for loans with a fixed-rate period.
+    pub term_payment_after_fixed_rate: Option<f64>,
+    /// The effective annual interest rate in percent, reflecting fees and compounding.
+    /// Not present when the rate is out of range, see [`effective_annual_rate`].
+    pub effective_annual_rate: Option<f64>,
 
     /// The date this loan was disbursed.
     pub disbursement_date: NaiveDate,
@@ -26,8 +47,736 @@ pub struct TotalResult {
     pub first_installment_date: NaiveDate,
     /// The date this loan was completely payed back.
     pub end_date: NaiveDate,
-    /// The number of total planned terms as of initial loan, transfer or refinance situation.
+    /// The number of total planned repayment terms as of initial loan, transfer or
+    /// refinance situation. Interest-only terms are not included.
     pub planned_terms: i32,
-    /// The number of total planned terms as of initial loan, transfer or refinance situation.
+    /// The number of repayment terms completed over the lifetime of the loan.
     pub completed_terms: i32,
+    /// The number of interest-only terms payed over the lifetime of the loan,
+    /// both the initial interest-only terms and those of any repayment freeze.
+    pub interest_only_terms: i32,
+    /// The number of interest changes held at the cap of the nominal interest.
+    pub rate_cap_triggers: i32,
+    /// The number of interest changes held at the floor of the nominal interest.
+    pub rate_floor_triggers: i32,
+    /// The number of interest changes held at the change limit of a single reset.
+    pub rate_change_limit_triggers: i32,
+}
+
+/// Notable events that occurred on a single date of the loan.
+#[derive(Clone, Debug)]
+pub enum NotableEvents {
+    /// The loan was disbursed with the provided amount.
+    Initialization(f64),
+    /// A scheduled repayment installment was payed with the provided amount.
+    RepaymentInstallment(f64),
+    /// A scheduled interest-only installment was payed with the provided amount.
+    InterestOnlyInstallment(f64),
+    /// An extra installment was payed with the provided amount.
+    ExtraInstallment(f64),
+    /// The residual balloon of the loan was payed with the provided amount.
+    BalloonInstallment(f64),
+    /// A fee for repaying the loan ahead of its schedule was payed with the provided amount.
+    PrepaymentFee(f64),
+    /// The fixed-rate period ended, and the loan continues at the provided floating interest.
+    FixedRateEnd(f64),
+    /// The nominal interest changed to the provided interest.
+    InterestChange(f64),
+    /// The nominal interest was restricted by the provided limit, from the provided
+    /// requested interest.
+    RateLimited(RateLimit, f64),
+    /// The loan was refinanced, increased by the provided amount.
+    Refinance(f64),
+    /// The loan was transferred to another bank with the provided outstanding loan.
+    BankTransfer(f64),
+    /// The loan was payed off ahead of its schedule, as itemized by the quote.
+    EarlyPayoff(PayoffQuote),
+}
+
+/// The state of the loan at the end of a single date.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LoanSnapshot {
+    /// The date of this snapshot.
+    pub date: NaiveDate,
+
+    /// The outstanding principal loan sum, including any compounded interest.
+    pub outstanding_loan: f64,
+    /// The interest accrued, not yet compounded into the outstanding loan.
+    pub accrued_interest: f64,
+    /// The interest accrued since the last installment, to be payed by the next installment.
+    pub interest_since_last_installment: f64,
+
+    /// The nominal interest in effect.
+    pub nominal_interest: f64,
+    /// The regular term payment, excluding the installment fee. For serial loans,
+    /// this is the repayment portion of each term, excluding the interest.
+    pub term_payment: f64,
+    /// The fee payed with every scheduled installment.
+    pub installment_fee: f64,
+    /// The number of terms per year.
+    pub terms_per_year: TermsPerYear,
+
+    /// The number of planned repayment terms, excluding interest-only terms.
+    pub planned_terms: u32,
+    /// The number of repayment terms completed.
+    pub completed_terms: u32,
+    /// The number of upcoming interest-only terms, both the initial interest-only terms
+    /// and those of any repayment freeze.
+    pub pending_interest_only_terms: u32,
+    /// The date of the next scheduled installment, moved to a business day.
+    /// Not present once the loan is repayed in full.
+    pub next_installment_date: Option<NaiveDate>,
+    /// Whether the loan is repayed in full on or before this date.
+    pub finished: bool,
+}
+
+/// The amount required to repay the entire loan on a single date.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PayoffQuote {
+    /// The date of the payoff.
+    pub date: NaiveDate,
+    /// The outstanding principal loan sum, including any interest compounded into it.
+    pub outstanding_principal: f64,
+    /// The interest accrued since the last installment, up to and including the payoff date.
+    pub accrued_interest: f64,
+    /// The administration fee charged for closing the loan.
+    pub administration_fee: f64,
+    /// The fee charged for repaying the outstanding loan early.
+    pub prepayment_fee: f64,
+    /// The total amount due on the payoff date.
+    pub total: f64,
+}
+
+/// The daily result produced by a Calculator.
+/// All fields here represent the state on the date of the status report.
+#[derive(Clone, Debug)]
+pub struct Daily {
+    /// The date of this Daily status report.
+    pub date: NaiveDate,
+
+    /// The amount of interest accrued on this date, including any quiet days since the
+    /// previous report that were not processed on their own.
+    pub accrued_interest: f64,
+    /// If any interest was compounded into the principal loan, this is represented here.
+    pub compounded_interest: f64,
+    /// The amount disbursed on the loan.
+    pub disbursed: f64,
+    /// The total number of fees accrued
+    pub fee: f64,
+    /// If anything was repayed to the loan, it is reflected in this status.
+    pub repayed: f64,
+    /// The portion of the repayed status that is an ordrinary repayment portion.
+    pub repayment_installment: f64,
+    /// The portion of the repayed status that is the accrued interest portion.
+    pub interest_installment: f64,
+    /// The portion of the repayed status that is the installment fee.
+    pub installment_fee: f64,
+    /// The portion of the repayed status that is a fee for prepaying the loan,
+    /// either by an extra installment or a transfer to another bank.
+    pub prepayment_fee: f64,
+    /// The portion of the repayed status that is due to extraordinary installment.
+    pub extra_installment: f64,
+    /// The portion of the repayment installment that is the residual balloon of the loan.
+    pub balloon_installment: f64,
+    /// The portion of the repayed status that repays the outstanding loan by an early payoff.
+    pub payoff_installment: f64,
+    /// The portion of the repayed status that is the administration fee of an early payoff.
+    pub payoff_fee: f64,
+
+    /// The outstanding principal loan sum after all actions on this date are processed.
+    pub outstanding_loan: f64,
+
+    /// Notable events that occurred on this date.
+    pub notable_events: Vec<NotableEvents>,
+}
+
+/// The aggregated payments of a single calendar year of the loan.
+///
+/// The first and last year of a loan are usually partial years,
+/// which is reflected by the `first_date` and `last_date` of the aggregation.
+#[derive(Clone, Debug)]
+pub struct YearlyResult {
+    /// The calendar year of this aggregation.
+    pub year: i32,
+    /// The first date within this year that had any activity on the loan.
+    pub first_date: NaiveDate,
+    /// The last date within this year that had any activity on the loan.
+    pub last_date: NaiveDate,
+
+    /// The amount disbursed within this year, either the initial loan or any refinancing.
+    pub disbursed: f64,
+    /// The interest portion of installments payed within this year.
+    pub interest: f64,
+    /// The ordinary repayment portion of installments payed within this year.
+    pub principal: f64,
+    /// The installment, prepayment and early payoff fees payed within this year.
+    pub fee: f64,
+    /// The extra installments and early payoff payed within this year.
+    pub extra: f64,
+
+    /// The outstanding principal loan sum at the end of the last active date this year.
+    pub closing_outstanding_loan: f64,
+}
+
+impl YearlyResult {
+    /// Aggregate a daily ledger into one result per calendar year.
+    ///
+    /// The ledger is expected to be ordered by date, as produced by
+    /// [`InteractiveCalculator::ledger`](crate::InteractiveCalculator::ledger).
+    pub fn from_ledger(ledger: &[Daily]) -> Vec<YearlyResult> {
+        let mut yearly: Vec<YearlyResult> = Vec::new();
+
+        for daily in ledger.iter() {
+            let year = match yearly.last_mut() {
+                Some(y) if y.year == daily.date.year() => y,
+                _ => {
+                    yearly.push(YearlyResult {
+                        year: daily.date.year(),
+                        first_date: daily.date,
+                        last_date: daily.date,
+                        disbursed: 0.0,
+                        interest: 0.0,
+                        principal: 0.0,
+                        fee: 0.0,
+                        extra: 0.0,
+                        closing_outstanding_loan: 0.0,
+                    });
+                    // SAFETY(unwrap): we just pushed an element.
+                    yearly.last_mut().unwrap()
+                }
+            };
+
+            year.last_date = daily.date;
+            year.disbursed += daily.disbursed;
+            year.interest += daily.interest_installment;
+            year.principal += daily.repayment_installment;
+            year.fee += daily.installment_fee + daily.prepayment_fee + daily.payoff_fee;
+            year.extra += daily.extra_installment + daily.payoff_installment;
+            year.closing_outstanding_loan = daily.outstanding_loan;
+        }
+
+        yearly
+    }
+
+    /// The total amount payed within this year.
+    pub fn total_payed(&self) -> f64 {
+        self.interest + self.principal + self.fee + self.extra
+    }
+}
+
+/// The cash flows of a single calendar month of the loan.
+///
+/// Every month from the first to the last date of the ledger is included,
+/// also those without any activity on the loan.
+#[derive(Clone, Debug)]
+pub struct MonthlyCashFlow {
+    /// The calendar year of this aggregation.
+    pub year: i32,
+    /// The calendar month of this aggregation, from 1 to 12.
+    pub month: u32,
+
+    /// The money out, disbursed within this month by the initial loan or any refinancing.
+    pub disbursed: f64,
+    /// The interest portion of installments payed within this month.
+    pub interest: f64,
+    /// The ordinary repayment portion of installments payed within this month.
+    pub principal: f64,
+    /// The installment, prepayment and early payoff fees payed within this month.
+    pub fee: f64,
+    /// The extra installments and early payoff payed within this month.
+    pub extra: f64,
+
+    /// The outstanding principal loan sum at the end of this month.
+    pub closing_outstanding_loan: f64,
+}
+
+impl MonthlyCashFlow {
+    /// Aggregate a daily ledger into one cash flow per calendar month.
+    ///
+    /// The ledger is expected to be ordered by date, as produced by
+    /// [`InteractiveCalculator::ledger`](crate::InteractiveCalculator::ledger).
+    pub fn from_ledger(ledger: &[Daily]) -> Vec<MonthlyCashFlow> {
+        let mut monthly: Vec<MonthlyCashFlow> = Vec::new();
+
+        for daily in ledger.iter() {
+            let (year, month) = (daily.date.year(), daily.date.month());
+
+            if monthly.is_empty() {
+                monthly.push(MonthlyCashFlow::empty(year, month, 0.0));
+            }
+
+            // Months without any activity keep the balance of the previous month.
+            while let Some(last) = monthly.last().filter(|m| (m.year, m.month) < (year, month)) {
+                let (next_year, next_month) = match last.month {
+                    12 => (last.year + 1, 1),
+                    month => (last.year, month + 1),
+                };
+                let closing_outstanding_loan = last.closing_outstanding_loan;
+                monthly.push(MonthlyCashFlow::empty(
+                    next_year,
+                    next_month,
+                    closing_outstanding_loan,
+                ));
+            }
+
+            // SAFETY(unwrap): the month of this date is the last element.
+            let current = monthly.last_mut().unwrap();
+            current.disbursed += daily.disbursed;
+            current.interest += daily.interest_installment;
+            current.principal += daily.repayment_installment;
+            current.fee += daily.installment_fee + daily.prepayment_fee + daily.payoff_fee;
+            current.extra += daily.extra_installment + daily.payoff_installment;
+            current.closing_outstanding_loan = daily.outstanding_loan;
+        }
+
+        monthly
+    }
+
+    fn empty(year: i32, month: u32, closing_outstanding_loan: f64) -> MonthlyCashFlow {
+        MonthlyCashFlow {
+            year,
+            month,
+            disbursed: 0.0,
+            interest: 0.0,
+            principal: 0.0,
+            fee: 0.0,
+            extra: 0.0,
+            closing_outstanding_loan,
+        }
+    }
+
+    /// The money in by installments within this month, being the interest,
+    /// principal and fees.
+    pub fn installments(&self) -> f64 {
+        self.interest + self.principal + self.fee
+    }
+
+    /// The total amount payed within this month, including extra payments.
+    pub fn total_payed(&self) -> f64 {
+        self.installments() + self.extra
+    }
+}
+
+/// The annual statement of a loan for taxation, being what was payed within a calendar year,
+/// where the interest payed is deductible in Norway.
+///
+/// A loan transferred to another bank has several lenders within the year of the transfer,
+/// each reported by a period of its own.
+#[derive(Clone, Debug)]
+pub struct TaxStatement {
+    /// The calendar year of this statement.
+    pub year: i32,
+    /// The periods of the year with every lender of the loan, ordered by date.
+    pub lenders: Vec<LenderPeriod>,
+}
+
+/// The payments to a single lender within a calendar year.
+#[derive(Clone, Debug)]
+pub struct LenderPeriod {
+    /// The lender of this period, counting from 0 for the lender disbursing the loan
+    /// and increased by every transfer to another bank.
+    pub lender: u32,
+    /// The first date within the year with any activity on the loan with this lender.
+    pub first_date: NaiveDate,
+    /// The last date within the year with any activity on the loan with this lender.
+    /// The interest accrued with a lender is settled on the date of a transfer,
+    /// which is the last date with that lender.
+    pub last_date: NaiveDate,
+
+    /// The interest payed to the lender.
+    pub interest: f64,
+    /// The installment, prepayment and early payoff fees payed to the lender.
+    pub fee: f64,
+    /// The principal repaid to the lender, by regular, extra and early payoff installments.
+    pub principal: f64,
+    /// The outstanding loan at the end of the period, being the balance on 31 Dec
+    /// or the balance transferred to the next lender.
+    pub closing_balance: f64,
+}
+
+impl TaxStatement {
+    /// Aggregate a daily ledger into one statement per calendar year.
+    ///
+    /// The ledger is expected to be ordered by date, as produced by
+    /// [`InteractiveCalculator::ledger`](crate::InteractiveCalculator::ledger).
+    pub fn from_ledger(ledger: &[Daily]) -> Vec<TaxStatement> {
+        let mut statements: Vec<TaxStatement> = Vec::new();
+        let mut lender = 0;
+
+        for daily in ledger.iter() {
+            let statement = match statements.last_mut() {
+                Some(s) if s.year == daily.date.year() => s,
+                _ => {
+                    statements.push(TaxStatement {
+                        year: daily.date.year(),
+                        lenders: Vec::new(),
+                    });
+                    // SAFETY(unwrap): we just pushed an element.
+                    statements.last_mut().unwrap()
+                }
+            };
+            let period = match statement.lenders.last_mut() {
+                Some(p) if p.lender == lender => p,
+                _ => {
+                    statement.lenders.push(LenderPeriod {
+                        lender,
+                        first_date: daily.date,
+                        last_date: daily.date,
+                        interest: 0.0,
+                        fee: 0.0,
+                        principal: 0.0,
+                        closing_balance: 0.0,
+                    });
+                    // SAFETY(unwrap): we just pushed an element.
+                    statement.lenders.last_mut().unwrap()
+                }
+            };
+
+            period.last_date = daily.date;
+            period.interest += daily.interest_installment;
+            period.fee += daily.installment_fee + daily.prepayment_fee + daily.payoff_fee;
+            period.principal +=
+                daily.repayment_installment + daily.extra_installment + daily.payoff_installment;
+            period.closing_balance = daily.outstanding_loan;
+
+            if daily
+                .notable_events
+                .iter()
+                .any(|e| matches!(e, NotableEvents::BankTransfer(_)))
+            {
+                lender += 1;
+            }
+        }
+
+        statements
+    }
+
+    /// The interest payed within the year, to every lender.
+    pub fn interest(&self) -> f64 {
+        self.lenders.iter().map(|p| p.interest).sum()
+    }
+
+    /// The fees payed within the year, to every lender.
+    pub fn fee(&self) -> f64 {
+        self.lenders.iter().map(|p| p.fee).sum()
+    }
+
+    /// The principal repaid within the year, to every lender.
+    pub fn principal(&self) -> f64 {
+        self.lenders.iter().map(|p| p.principal).sum()
+    }
+
+    /// The outstanding loan on 31 Dec, which is zero when the loan is repaid within the year.
+    pub fn closing_balance(&self) -> f64 {
+        self.lenders.last().map_or(0.0, |p| p.closing_balance)
+    }
+}
+
+/// Extract a single portion of the yearly payments.
+type YearlyPortion = fn(&YearlyResult) -> f64;
+
+/// Render a stacked bar chart of the yearly payments onto the drawing area.
+///
+/// Each bar is divided into principal, interest, fee and extra installment portions.
+pub fn draw_yearly_breakdown<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    yearly: &[YearlyResult],
+) -> Result<(), String> {
+    let (first_year, last_year) = match (yearly.first(), yearly.last()) {
+        (Some(f), Some(l)) => (f.year, l.year),
+        _ => return Err("no yearly results to draw".to_string()),
+    };
+    let max_payed = yearly.iter().map(|y| y.total_payed()).fold(0.0, f64::max);
+
+    // Each portion is stacked on top of the previous ones, in this order.
+    let portions: [(&str, &RGBColor, YearlyPortion); 4] = [
+        ("Principal", &BLUE, |y| y.principal),
+        ("Interest", &RED, |y| y.interest),
+        ("Fee", &MAGENTA, |y| y.fee),
+        ("Extra", &GREEN, |y| y.extra),
+    ];
+
+    root.fill(&WHITE).map_err(|e| e.to_string())?;
+
+    let mut chart = ChartBuilder::on(root)
+        .caption("Yearly Payment Breakdown", ("sans-serif", 40).into_font())
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(80)
+        .build_cartesian_2d(
+            (first_year..last_year + 1).into_segmented(),
+            0f64..max_payed * 1.1,
+        )
+        .map_err(|e| e.to_string())?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .y_label_formatter(&|y| format!("{:.0}", y))
+        .draw()
+        .map_err(|e| e.to_string())?;
+
+    for (index, (label, color, portion)) in portions.iter().enumerate() {
+        let color = *color;
+        chart
+            .draw_series(yearly.iter().map(|y| {
+                let bottom: f64 = portions[..index].iter().map(|(_, _, p)| p(y)).sum();
+                let top = bottom + portion(y);
+                let mut bar = Rectangle::new(
+                    [
+                        (SegmentValue::Exact(y.year), bottom),
+                        (SegmentValue::Exact(y.year + 1), top),
+                    ],
+                    color.filled(),
+                );
+                bar.set_margin(0, 0, 5, 5);
+                bar
+            }))
+            .map_err(|e| e.to_string())?
+            .label(*label)
+            .legend(move |(x, y)| Rectangle::new([(x, y - 5), (x + 10, y + 5)], color.filled()));
+    }
+
+    chart
+        .configure_series_labels()
+        .background_style(&WHITE.mix(0.8))
+        .border_style(&BLACK)
+        .draw()
+        .map_err(|e| e.to_string())?;
+
+    root.present().map_err(|e| e.to_string())
+}
+
+/// Render the outstanding balance and the cumulative cost of a loan over its lifetime
+/// onto the drawing area.
+///
+/// Any other scenarios, such as the original plan or a compared scenario, are drawn as
+/// thinner lines on the same axes, labelled by their name.
+pub fn draw_balance<DB: DrawingBackend>(
+    root: &DrawingArea<DB, Shift>,
+    ledger: &[Daily],
+    overlays: &[(String, Vec<Daily>)],
+) -> Result<(), String> {
+    let series: Vec<Vec<(NaiveDate, f64, f64)>> = std::iter::once(ledger)
+        .chain(overlays.iter().map(|(_, ledger)| ledger.as_slice()))
+        .map(|ledger| {
+            let mut cost = 0.0;
+            ScheduleEntry::from_ledger(ledger)
+                .into_iter()
+                .map(|e| {
+                    cost += e.payment;
+                    (e.date, e.outstanding_loan.max(0.0), cost)
+                })
+                .collect()
+        })
+        .collect();
+
+    let points = || series.iter().flat_map(|s| s.iter());
+    let (first, last) = match (points().map(|p| p.0).min(), points().map(|p| p.0).max()) {
+        (Some(first), Some(last)) if !series[0].is_empty() => (first, last.max(first.succ())),
+        _ => return Err("no schedule to draw".to_string()),
+    };
+    let max = points().map(|p| p.1.max(p.2)).fold(0.0, f64::max);
+
+    root.fill(&WHITE).map_err(|e| e.to_string())?;
+
+    let mut chart = ChartBuilder::on(root)
+        .margin(10)
+        .x_label_area_size(30)
+        .y_label_area_size(80)
+        .build_cartesian_2d(first..last, 0f64..(max * 1.05).max(1.0))
+        .map_err(|e| e.to_string())?;
+
+    chart
+        .configure_mesh()
+        .disable_x_mesh()
+        .x_label_formatter(&|date| date.year().to_string())
+        .y_label_formatter(&|y| format!("{:.0}", y))
+        .draw()
+        .map_err(|e| e.to_string())?;
+
+    // The primary scenario is drawn on top of the overlays, with both its curves named.
+    let overlay_colors: [&RGBColor; 3] = [&GREEN, &MAGENTA, &CYAN];
+    let lines = overlays
+        .iter()
+        .zip(overlay_colors.iter().cycle())
+        .enumerate()
+        .map(|(i, ((name, _), color))| (&series[i + 1], name.as_str(), "", *color, *color, 1))
+        .chain(std::iter::once((
+            &series[0],
+            "Balance",
+            "Cumulative cost",
+            &RED,
+            &BLUE,
+            2,
+        )));
+    for (points, balance_label, cost_label, balance_color, cost_color, width) in lines {
+        let balance = chart
+            .draw_series(LineSeries::new(
+                points.iter().map(|p| (p.0, p.1)),
+                balance_color.stroke_width(width),
+            ))
+            .map_err(|e| e.to_string())?;
+        balance.label(balance_label).legend(move |(x, y)| {
+            PathElement::new(vec![(x, y), (x + 15, y)], balance_color.stroke_width(2))
+        });
+
+        let cost = chart
+            .draw_series(LineSeries::new(
+                points.iter().map(|p| (p.0, p.2)),
+                cost_color.stroke_width(width),
+            ))
+            .map_err(|e| e.to_string())?;
+        if !cost_label.is_empty() {
+            cost.label(cost_label).legend(move |(x, y)| {
+                PathElement::new(vec![(x, y), (x + 15, y)], cost_color.stroke_width(2))
+            });
+        }
+    }
+
+    chart
+        .configure_series_labels()
+        .position(SeriesLabelPosition::UpperRight)
+        .background_style(&WHITE.mix(0.8))
+        .border_style(&BLACK)
+        .draw()
+        .map_err(|e| e.to_string())?;
+
+    root.present().map_err(|e| e.to_string())
+}
+
+/// A single entry of the repayment schedule.
+///
+/// There is an entry for every scheduled installment, and for every other date
+/// where money changed hands or an event took effect on the loan.
+#[derive(Clone, Debug)]
+pub struct ScheduleEntry {
+    /// The date of this entry.
+    pub date: NaiveDate,
+    /// The total amount payed on this date.
+    pub payment: f64,
+    /// The interest portion of the payment.
+    pub interest: f64,
+    /// The ordinary repayment portion of the payment.
+    pub principal: f64,
+    /// The installment, prepayment and early payoff fee portion of the payment.
+    pub fee: f64,
+    /// The extra installment and early payoff portion of the payment.
+    pub extra: f64,
+    /// The portion of the principal that is the residual balloon of the loan.
+    pub balloon: f64,
+    /// The amount disbursed on this date.
+    pub disbursed: f64,
+    /// The outstanding principal loan sum after this entry.
+    pub outstanding_loan: f64,
+    /// Notable events that occurred on this date.
+    pub notable_events: Vec<NotableEvents>,
+}
+
+impl ScheduleEntry {
+    /// Construct the repayment schedule from a daily ledger.
+    pub fn from_ledger(ledger: &[Daily]) -> Vec<ScheduleEntry> {
+        ledger
+            .iter()
+            .filter(|d| !d.notable_events.is_empty())
+            .map(|d| ScheduleEntry {
+                date: d.date,
+                payment: d.repayed,
+                interest: d.interest_installment,
+                principal: d.repayment_installment,
+                fee: d.installment_fee + d.prepayment_fee + d.payoff_fee,
+                extra: d.extra_installment + d.payoff_installment,
+                balloon: d.balloon_installment,
+                disbursed: d.disbursed,
+                outstanding_loan: d.outstanding_loan,
+                notable_events: d.notable_events.clone(),
+            })
+            .collect()
+    }
+
+    /// Whether a scheduled interest-only installment was payed on this date,
+    /// either within the initial interest-only terms or a repayment freeze.
+    pub fn is_interest_only(&self) -> bool {
+        self.notable_events
+            .iter()
+            .any(|e| matches!(e, NotableEvents::InterestOnlyInstallment(_)))
+    }
+
+    /// The floating interest the loan continues at, if the fixed-rate period ended on this date.
+    pub fn fixed_rate_end(&self) -> Option<f64> {
+        self.notable_events.iter().find_map(|e| match e {
+            NotableEvents::FixedRateEnd(interest) => Some(*interest),
+            _ => None,
+        })
+    }
+
+    /// The limit restricting the nominal interest on this date, with the requested interest.
+    pub fn rate_limited(&self) -> Option<(RateLimit, f64)> {
+        self.notable_events.iter().find_map(|e| match e {
+            NotableEvents::RateLimited(limit, requested) => Some((*limit, *requested)),
+            _ => None,
+        })
+    }
+
+    /// The itemized payoff, if the loan was payed off ahead of its schedule on this date.
+    pub fn early_payoff(&self) -> Option<&PayoffQuote> {
+        self.notable_events.iter().find_map(|e| match e {
+            NotableEvents::EarlyPayoff(quote) => Some(quote),
+            _ => None,
+        })
+    }
+
+    /// Whether an event, other than a scheduled installment, took effect on this date.
+    pub fn is_event(&self) -> bool {
+        self.notable_events.iter().any(|e| {
+            !matches!(
+                e,
+                NotableEvents::RepaymentInstallment(_) | NotableEvents::InterestOnlyInstallment(_)
+            )
+        })
+    }
+}
+
+/// Compute the effective annual interest rate, in percent, of a daily ledger.
+///
+/// The effective rate is the annual discount rate at which the present value of every
+/// payment on the loan equals the present value of every disbursement. All fees and
+/// the compounding of interest are thereby reflected in the rate.
+pub fn effective_annual_rate(ledger: &[Daily]) -> Result<f64, String> {
+    let start = match ledger.first() {
+        Some(d) => d.date,
+        None => return Err("cannot compute the effective rate of an empty ledger".to_string()),
+    };
+
+    // The cash flows as seen from the lender, as (years since disbursement, amount).
+    let flows: Vec<(f64, f64)> = ledger
+        .iter()
+        .filter(|d| d.disbursed != 0.0 || d.repayed != 0.0)
+        .map(|d| {
+            let years = (d.date - start).num_days() as f64 / 365.0;
+            (years, d.repayed - d.disbursed)
+        })
+        .collect();
+    let present_value = |rate: f64| -> f64 {
+        flows
+            .iter()
+            .map(|(years, amount)| amount / (1.0 + rate).powf(*years))
+            .sum()
+    };
+
+    // The present value decreases with the rate, bisect until the root is found.
+    let (mut low, mut high) = (-0.99, 10.0);
+    if present_value(low) < 0.0 || present_value(high) > 0.0 {
+        return Err("the effective rate of the loan is out of range".to_string());
+    }
+    for _ in 0..100 {
+        let mid = (low + high) / 2.0;
+        if present_value(mid) > 0.0 {
+            low = mid;
+        } else {
+            high = mid;
+        }
+    }
+
+    Ok((low + high) / 2.0 * 100.0)
 }
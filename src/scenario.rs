@@ -0,0 +1,90 @@
+//! Persistence of a loan scenario, the complete event timeline of a calculator.
+//!
+//! Scenarios are stored as JSON, such that a scenario exported from the GUI may
+//! be read by the CLI and vice versa.
+
+use crate::calculator::{CompoundingStrategy, InteractiveCalculator};
+use crate::events::LoanEvent;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A named snapshot of every event of a calculator.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Scenario {
+    /// A descriptive name of the scenario, presented to the user.
+    pub name: String,
+    /// All events ordered by date, starting with the initial loan event.
+    pub events: Vec<ScenarioEvent>,
+    /// When the accrued interest is posted to the outstanding loan.
+    #[serde(default)]
+    pub compounding_strategy: CompoundingStrategy,
+}
+
+/// A single event of a scenario, along with the date it takes effect.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ScenarioEvent {
+    pub date: NaiveDate,
+    pub event: LoanEvent,
+}
+
+impl Scenario {
+    /// Take a snapshot of the events of calculator.
+    pub fn from_calculator(name: &str, calculator: &InteractiveCalculator) -> Scenario {
+        Scenario {
+            name: name.to_string(),
+            events: calculator
+                .events()
+                .map(|(date, event)| ScenarioEvent {
+                    date,
+                    event: event.clone(),
+                })
+                .collect(),
+            compounding_strategy: calculator.compounding_strategy(),
+        }
+    }
+
+    /// Construct a calculator with every event of this scenario.
+    ///
+    /// The first event must be the initial loan event,
+    /// and every other event must take place after the disbursement date.
+    pub fn to_calculator(&self) -> Result<InteractiveCalculator, String> {
+        let mut events = self.events.iter();
+        let mut calculator = match events.next() {
+            Some(ScenarioEvent {
+                date,
+                event: LoanEvent::Initial(initial),
+            }) => InteractiveCalculator::new(*date, initial.clone()),
+            _ => return Err("a scenario must start with the initial loan event".to_string()),
+        };
+
+        for e in events {
+            calculator.add_event(e.date, e.event.clone())?;
+        }
+        calculator.set_compounding_strategy(self.compounding_strategy);
+
+        Ok(calculator)
+    }
+
+    pub fn to_json(&self) -> Result<String, String> {
+        serde_json::to_string_pretty(self).map_err(|e| e.to_string())
+    }
+
+    pub fn from_json(json: &str) -> Result<Scenario, String> {
+        serde_json::from_str(json).map_err(|e| format!("invalid scenario: {}", e))
+    }
+
+    /// Write the scenario as JSON to the file at path.
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        std::fs::write(path, self.to_json()?)
+            .map_err(|e| format!("failed to write {}: {}", path.display(), e))
+    }
+
+    /// Read a scenario from a JSON file at path.
+    pub fn load(path: &Path) -> Result<Scenario, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("failed to read {}: {}", path.display(), e))?;
+        Scenario::from_json(&json)
+    }
+}
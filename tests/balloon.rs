@@ -0,0 +1,79 @@
+use interest_calculator::{Balloon, InteractiveCalculator, LoanInitialization, ScheduleEntry};
+
+use chrono::{Month, NaiveDate};
+
+fn car_loan(balloon: Option<Balloon>) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 100000.0,
+        nominal_interest: 5.0,
+        terms: 36,
+        first_installment_month: Month::February,
+        balloon,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(NaiveDate::from_ymd(2021, 1, 1), initial)
+}
+
+#[test]
+fn balloon_lowers_term_payment() {
+    let amortised = car_loan(None).compute().unwrap();
+    let balloon = car_loan(Some(Balloon::Percentage(20.0))).compute().unwrap();
+
+    assert_eq!(amortised.total_balloon_installment, 0.0);
+    assert!(balloon.term_payment < amortised.term_payment);
+    assert!(balloon.total_interest > amortised.total_interest);
+
+    // The balloon is due with the last planned term. The term payment is based on the
+    // effective interest, hence the residual is slightly lower than the balloon.
+    assert_eq!(balloon.end_date, amortised.end_date);
+    assert_eq!(balloon.completed_terms, 36);
+    assert!(
+        (balloon.total_balloon_installment - 20000.0).abs() < 400.0,
+        "{}",
+        balloon.total_balloon_installment
+    );
+    assert!((balloon.total_repayment_installment - 100000.0).abs() < 1e-6);
+}
+
+#[test]
+fn balloon_amount_equals_percentage() {
+    let percentage = car_loan(Some(Balloon::Percentage(25.0))).compute().unwrap();
+    let amount = car_loan(Some(Balloon::Amount(25000.0))).compute().unwrap();
+
+    assert_eq!(percentage.term_payment, amount.term_payment);
+    assert_eq!(
+        percentage.total_balloon_installment,
+        amount.total_balloon_installment
+    );
+}
+
+#[test]
+fn balloon_in_schedule() {
+    let ledger = car_loan(Some(Balloon::Amount(30000.0))).ledger().unwrap();
+    let schedule = ScheduleEntry::from_ledger(&ledger);
+
+    let last = schedule.last().unwrap();
+    assert!(last.is_event());
+    assert!(last.balloon > 29000.0);
+    assert!(last.principal >= last.balloon);
+    assert!(last.outstanding_loan.abs() < 1e-6);
+
+    // No other entry carries a balloon.
+    assert!(schedule[..schedule.len() - 1]
+        .iter()
+        .all(|e| e.balloon == 0.0));
+}
+
+#[test]
+fn balloon_must_be_less_than_loan() {
+    assert!(car_loan(Some(Balloon::Amount(100000.0))).compute().is_err());
+    assert!(car_loan(Some(Balloon::Percentage(-5.0))).compute().is_err());
+}
+
+#[test]
+fn balloon_from_str() {
+    assert_eq!("20%".parse(), Ok(Balloon::Percentage(20.0)));
+    assert_eq!("15000".parse(), Ok(Balloon::Amount(15000.0)));
+    assert!("twenty".parse::<Balloon>().is_err());
+}
@@ -16,10 +16,11 @@ fn interactive_calculator_initial_event() {
         terms_per_year: TermsPerYear::Twelve,
         due_within_month: MonthlyDueDate::First,
         first_installment_month: Month::February,
+        ..LoanInitialization::default()
     };
 
     let loan_start_date = NaiveDate::from_ymd(2021, 1, 10);
-    let calculator = InteractiveCalculator::new(loan_start_date.clone(), initial);
+    let calculator = InteractiveCalculator::new(loan_start_date, initial);
 
     let summary = calculator.compute();
     assert!(summary.is_ok());
@@ -31,6 +32,39 @@ fn interactive_calculator_initial_event() {
     assert_eq!(summary.end_date, NaiveDate::from_ymd(2022, 1, 1));
 }
 
+#[test]
+fn interactive_calculator_term_payment() {
+    let initial = LoanInitialization {
+        loan: 1000.0,
+        nominal_interest: 1.0,
+        administration_fee: 0.0,
+        installment_fee: 0.0,
+        terms: 12,
+        terms_per_year: TermsPerYear::Twelve,
+        due_within_month: MonthlyDueDate::First,
+        first_installment_month: Month::February,
+        ..LoanInitialization::default()
+    };
+
+    let loan_start_date = NaiveDate::from_ymd(2021, 1, 10);
+    let calculator = InteractiveCalculator::new(loan_start_date, initial);
+    let summary = calculator.compute().unwrap();
+
+    assert_eq!(
+        summary.first_installment_date,
+        NaiveDate::from_ymd(2021, 2, 1)
+    );
+    assert_eq!(calculator.interest_saved_by_extra().unwrap(), 0.0);
+
+    // The annuity of 1000 over 12 terms at 1% interest.
+    assert!(
+        (summary.term_payment - 83.79).abs() < 0.05,
+        "{}",
+        summary.term_payment
+    );
+    assert!((summary.effective_annual_rate.unwrap() - 1.0).abs() < 0.05);
+}
+
 #[test]
 fn outstanding_loan_is_negative() {
     let initial = LoanInitialization {
@@ -42,6 +76,7 @@ fn outstanding_loan_is_negative() {
         terms_per_year: TermsPerYear::Twelve,
         due_within_month: MonthlyDueDate::First,
         first_installment_month: Month::February,
+        ..LoanInitialization::default()
     };
 
     let loan_start_date = NaiveDate::from_ymd(2021, 1, 10);
@@ -66,6 +101,7 @@ fn outstanding_loan_is_zero() {
         terms_per_year: TermsPerYear::Twelve,
         due_within_month: MonthlyDueDate::First,
         first_installment_month: Month::February,
+        ..LoanInitialization::default()
     };
 
     let loan_start_date = NaiveDate::from_ymd(2021, 1, 10);
@@ -90,6 +126,7 @@ fn interest_is_zero() {
         terms_per_year: TermsPerYear::Twelve,
         due_within_month: MonthlyDueDate::First,
         first_installment_month: Month::February,
+        ..LoanInitialization::default()
     };
 
     let loan_start_date = NaiveDate::from_ymd(2021, 1, 10);
@@ -114,6 +151,7 @@ fn interest_is_negative() {
         terms_per_year: TermsPerYear::Twelve,
         due_within_month: MonthlyDueDate::First,
         first_installment_month: Month::February,
+        ..LoanInitialization::default()
     };
 
     let loan_start_date = NaiveDate::from_ymd(2021, 1, 10);
@@ -126,3 +164,31 @@ fn interest_is_negative() {
         &res.unwrap_err()
     );
 }
+
+#[test]
+fn installment_fee_is_not_repayment() {
+    let initial = LoanInitialization {
+        loan: 1000.0,
+        nominal_interest: 1.0,
+        administration_fee: 0.0,
+        installment_fee: 10.0,
+        terms: 12,
+        terms_per_year: TermsPerYear::Twelve,
+        due_within_month: MonthlyDueDate::First,
+        first_installment_month: Month::February,
+        ..LoanInitialization::default()
+    };
+
+    let loan_start_date = NaiveDate::from_ymd(2021, 1, 10);
+    let calculator = InteractiveCalculator::new(loan_start_date, initial);
+    let summary = calculator.compute().unwrap();
+
+    // The installment fees are payed on top of the repayment of the loan.
+    assert!(
+        (summary.total_repayment_installment - 1000.0).abs() < 1e-6,
+        "{}",
+        summary.total_repayment_installment
+    );
+    assert!((summary.total_fee - 120.0).abs() < 1e-6);
+    assert_eq!(summary.end_date, NaiveDate::from_ymd(2022, 1, 1));
+}
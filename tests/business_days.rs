@@ -0,0 +1,136 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    easter_sunday, BusinessDayConvention, HolidayCalendar, InteractiveCalculator,
+    LoanInitialization, MonthlyDueDate, NorwegianCalendar, WeekendCalendar,
+};
+
+use chrono::{Datelike, NaiveDate, Weekday};
+
+fn loan(
+    due_within_month: MonthlyDueDate,
+    business_day_convention: BusinessDayConvention,
+) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 500000.0,
+        nominal_interest: 3.0,
+        terms: 24,
+        due_within_month,
+        business_day_convention,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(date(2021, 12, 10), initial)
+}
+
+/// The dates of every scheduled repayment installment.
+fn installment_dates(calculator: &InteractiveCalculator) -> Vec<NaiveDate> {
+    calculator
+        .ledger()
+        .unwrap()
+        .iter()
+        .filter(|d| d.repayment_installment > 0.0)
+        .map(|d| d.date)
+        .collect()
+}
+
+#[test]
+fn norwegian_holidays() {
+    assert_eq!(easter_sunday(2000), date(2000, 4, 23));
+    assert_eq!(easter_sunday(2019), date(2019, 4, 21));
+    assert_eq!(easter_sunday(2024), date(2024, 3, 31));
+    assert_eq!(easter_sunday(2025), date(2025, 4, 20));
+
+    let calendar = NorwegianCalendar;
+    let holidays = [
+        date(2025, 1, 1),
+        date(2025, 4, 17),
+        date(2025, 4, 18),
+        date(2025, 4, 20),
+        date(2025, 4, 21),
+        date(2025, 5, 1),
+        date(2025, 5, 17),
+        date(2025, 5, 29),
+        date(2025, 6, 8),
+        date(2025, 6, 9),
+        date(2025, 12, 25),
+        date(2025, 12, 26),
+    ];
+    for holiday in holidays.iter() {
+        assert!(calendar.is_holiday(*holiday), "{}", holiday);
+    }
+    assert!(!calendar.is_holiday(date(2025, 4, 22)));
+    assert!(!calendar.is_business_day(date(2025, 4, 26)));
+    assert!(calendar.is_business_day(date(2025, 4, 22)));
+}
+
+#[test]
+fn business_day_conventions() {
+    let calendar = NorwegianCalendar;
+    let adjust = |convention: BusinessDayConvention, d| convention.adjust(d, &calendar);
+
+    // New Year's Day on a Saturday.
+    let new_year = date(2022, 1, 1);
+    assert_eq!(adjust(BusinessDayConvention::None, new_year), new_year);
+    assert_eq!(
+        adjust(BusinessDayConvention::Following, new_year),
+        date(2022, 1, 3)
+    );
+    assert_eq!(
+        adjust(BusinessDayConvention::Preceding, new_year),
+        date(2021, 12, 31)
+    );
+
+    // The last Saturday of April, followed by Labour Day on a Sunday.
+    let end_of_april = date(2022, 4, 30);
+    assert_eq!(
+        adjust(BusinessDayConvention::Following, end_of_april),
+        date(2022, 5, 2)
+    );
+    assert_eq!(
+        adjust(BusinessDayConvention::ModifiedFollowing, end_of_april),
+        date(2022, 4, 29)
+    );
+
+    for convention in BusinessDayConvention::ALL.iter() {
+        assert_eq!(convention.to_string().parse(), Ok(*convention));
+    }
+}
+
+#[test]
+fn due_dates_moved_to_business_days() {
+    let unadjusted = loan(MonthlyDueDate::First, BusinessDayConvention::None);
+    let following = loan(MonthlyDueDate::First, BusinessDayConvention::Following);
+
+    let dates = installment_dates(&following);
+    assert_eq!(dates.len(), 24);
+    assert_eq!(dates[0], date(2022, 1, 3));
+    // The schedule continues from the due date, rather than the moved date.
+    assert_eq!(dates[1], date(2022, 2, 1));
+    assert!(dates.iter().all(|d| NorwegianCalendar.is_business_day(*d)));
+    assert!(!installment_dates(&unadjusted)
+        .iter()
+        .all(|d| NorwegianCalendar.is_business_day(*d)));
+
+    // Moving the due dates changes the interest accrued between installments.
+    let unadjusted = unadjusted.compute().unwrap();
+    let following = following.compute().unwrap();
+    assert_ne!(unadjusted.total_interest, following.total_interest);
+    assert_eq!(following.first_installment_date, date(2022, 1, 3));
+    assert_eq!(unadjusted.completed_terms, following.completed_terms);
+}
+
+#[test]
+fn custom_holiday_calendar() {
+    // Constitution Day on a Tuesday is only a holiday in the Norwegian calendar.
+    let mut calculator = loan(MonthlyDueDate::Date(17), BusinessDayConvention::Following);
+    assert!(installment_dates(&calculator).contains(&date(2022, 5, 18)));
+
+    calculator.set_holiday_calendar(WeekendCalendar);
+    let dates = installment_dates(&calculator);
+    assert!(dates.contains(&date(2022, 5, 17)));
+    assert!(dates
+        .iter()
+        .all(|d| !matches!(d.weekday(), Weekday::Sat | Weekday::Sun)));
+}
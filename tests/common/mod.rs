@@ -0,0 +1,10 @@
+//! Fixtures shared by the integration tests.
+
+// Every test file only uses some of the fixtures.
+#![allow(dead_code)]
+
+use chrono::NaiveDate;
+
+pub fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+    NaiveDate::from_ymd(y, m, d)
+}
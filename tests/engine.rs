@@ -0,0 +1,216 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    Amortisation, Balloon, BusinessDayConvention, CompoundingStrategy, Daily, Engine,
+    ExtraInstallmentPolicy, FloatingRate, InteractiveCalculator, LoanExtraInstallment,
+    LoanInitialization, LoanInterestChange, LoanPayoff, LoanRecurringExtraInstallments,
+    LoanRefinance, LoanRepaymentFreeze, LoanTransfer, MonthlyDueDate, PrepaymentFee,
+    RecurringInterval, TermsPerYear, YearlyResult,
+};
+
+use chrono::{Datelike, Month, Weekday};
+use std::num::NonZeroU32;
+
+fn initial(amortisation: Amortisation) -> LoanInitialization {
+    LoanInitialization {
+        loan: 2500000.0,
+        nominal_interest: 3.2,
+        administration_fee: 1000.0,
+        installment_fee: 50.0,
+        terms: 300,
+        due_within_month: MonthlyDueDate::Date(15),
+        first_installment_month: Month::March,
+        business_day_convention: BusinessDayConvention::Following,
+        interest_only_terms: 6,
+        amortisation,
+        ..LoanInitialization::default()
+    }
+}
+
+/// A loan with every kind of event along its lifetime.
+fn eventful(amortisation: Amortisation) -> InteractiveCalculator {
+    let mut initial = initial(amortisation);
+    initial.floating_rate = Some(FloatingRate::Path(vec![
+        (date(2024, 1, 1), 4.1),
+        (date(2027, 7, 1), 2.9),
+    ]));
+    initial.rate_limits.cap = Some(4.0);
+
+    let mut calculator = InteractiveCalculator::new(date(2021, 1, 20), initial);
+    calculator
+        .add_event_interest_change(
+            date(2022, 5, 4),
+            LoanInterestChange {
+                nominal_interest: 3.6,
+            },
+        )
+        .unwrap();
+    calculator
+        .add_event_extra_single(
+            date(2022, 8, 31),
+            LoanExtraInstallment {
+                amount: 100000.0,
+                policy: Some(ExtraInstallmentPolicy::LowerPayment),
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    calculator
+        .add_event_extra_recurring(
+            date(2023, 2, 3),
+            LoanRecurringExtraInstallments {
+                amount: 1500.0,
+                count: NonZeroU32::new(40).unwrap(),
+                recurring_interval: RecurringInterval::Monthly,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    calculator
+        .add_event_repayment_freeze(
+            date(2025, 6, 1),
+            LoanRepaymentFreeze {
+                count: NonZeroU32::new(3).unwrap(),
+            },
+        )
+        .unwrap();
+    calculator
+        .add_event_refinance(
+            date(2026, 10, 12),
+            LoanRefinance {
+                loan_increase: 300000.0,
+                administration_fee: 1500.0,
+            },
+        )
+        .unwrap();
+    calculator
+        .add_event_bank_transfer(
+            date(2029, 3, 9),
+            LoanTransfer {
+                administration_fee: 2500.0,
+                prepayment_fee: Some(PrepaymentFee::Percentage(0.5)),
+            },
+        )
+        .unwrap();
+    calculator
+}
+
+/// The interest accrued within a calendar year.
+fn accrued(ledger: &[Daily], year: i32) -> f64 {
+    ledger
+        .iter()
+        .filter(|d| d.date.year() == year)
+        .map(|d| d.accrued_interest)
+        .sum()
+}
+
+/// Assert that both engines compute the same results, up to rounding of the interest.
+fn assert_same_results(calculator: &InteractiveCalculator) {
+    let mut daily = calculator.clone();
+    daily.set_engine(Engine::Daily);
+
+    let close = |a: f64, b: f64| (a - b).abs() < 1e-6;
+    let event_driven = calculator.compute().unwrap();
+    let every_day = daily.compute().unwrap();
+    assert!(close(event_driven.total_cost, every_day.total_cost));
+    assert!(close(event_driven.total_interest, every_day.total_interest));
+    assert!(close(event_driven.total_fee, every_day.total_fee));
+    assert!(close(
+        calculator.interest_saved_by_extra().unwrap(),
+        daily.interest_saved_by_extra().unwrap()
+    ));
+    assert!(close(
+        calculator.interest_saved_by_rate_limits().unwrap(),
+        daily.interest_saved_by_rate_limits().unwrap()
+    ));
+    assert!(close(event_driven.term_payment, every_day.term_payment));
+    assert_eq!(event_driven.end_date, every_day.end_date);
+    assert_eq!(event_driven.completed_terms, every_day.completed_terms);
+    assert_eq!(
+        event_driven.interest_only_terms,
+        every_day.interest_only_terms
+    );
+
+    // The interest is accrued within the year it belongs to.
+    let event_driven = calculator.ledger().unwrap();
+    let every_day = daily.ledger().unwrap();
+    let yearly = YearlyResult::from_ledger(&event_driven);
+    assert_eq!(yearly.len(), YearlyResult::from_ledger(&every_day).len());
+    for (a, b) in yearly
+        .iter()
+        .zip(YearlyResult::from_ledger(&every_day).iter())
+    {
+        assert_eq!((a.year, a.last_date), (b.year, b.last_date));
+        assert!(close(a.interest, b.interest));
+        assert!(close(
+            a.closing_outstanding_loan,
+            b.closing_outstanding_loan
+        ));
+        assert!(close(
+            accrued(&event_driven, a.year),
+            accrued(&every_day, a.year)
+        ));
+    }
+
+    // The event-driven ledger only holds the significant dates of the daily ledger.
+    assert!(event_driven.len() <= every_day.len());
+    for record in event_driven.iter() {
+        let same_day = every_day.iter().find(|d| d.date == record.date).unwrap();
+        assert!(close(record.outstanding_loan, same_day.outstanding_loan));
+        assert!(close(record.repayed, same_day.repayed));
+    }
+
+    for query in [date(2021, 6, 30), date(2026, 10, 12), date(2030, 2, 14)].iter() {
+        let a = calculator.snapshot(*query).unwrap();
+        let b = daily.snapshot(*query).unwrap();
+        assert!(close(a.outstanding_loan, b.outstanding_loan));
+        assert!(close(a.accrued_interest, b.accrued_interest));
+        assert_eq!(a.completed_terms, b.completed_terms);
+        assert_eq!(a.next_installment_date, b.next_installment_date);
+    }
+}
+
+#[test]
+fn engines_compute_the_same_loan() {
+    assert_same_results(&eventful(Amortisation::Annuity));
+    assert_same_results(&eventful(Amortisation::Serial));
+
+    let mut paid_off = eventful(Amortisation::Annuity);
+    paid_off
+        .add_event_early_payoff(date(2033, 4, 2), LoanPayoff::default())
+        .unwrap();
+    assert_same_results(&paid_off);
+}
+
+#[test]
+fn engines_compute_the_same_weekly_loan() {
+    let mut weekly = initial(Amortisation::Annuity);
+    weekly.terms = 520;
+    weekly.terms_per_year = TermsPerYear::FiftyTwo;
+    weekly.due_weekday = Some(Weekday::Fri);
+    weekly.balloon = Some(Balloon::Percentage(20.0));
+
+    assert_same_results(&InteractiveCalculator::new(date(2021, 1, 20), weekly));
+}
+
+#[test]
+fn engines_compound_the_same() {
+    let on_installment = eventful(Amortisation::Annuity).compute().unwrap();
+    for strategy in [
+        CompoundingStrategy::Daily,
+        CompoundingStrategy::EndOfMonth,
+        CompoundingStrategy::EndOfYear,
+    ]
+    .iter()
+    {
+        let mut calculator = eventful(Amortisation::Annuity);
+        calculator.set_compounding_strategy(*strategy);
+        assert_same_results(&calculator);
+
+        // Interest compounded between installments accrues interest of its own.
+        let compounded = calculator.compute().unwrap();
+        assert_ne!(compounded.total_interest, on_installment.total_interest);
+    }
+}
@@ -0,0 +1,360 @@
+use interest_calculator::{
+    InteractiveCalculator, LoanEvent, LoanExtraInstallment, LoanInitialization, LoanInterestChange,
+    LoanRecurringExtraInstallments, LoanRefinance, LoanRepaymentFreeze, LoanTransfer,
+    NotableEvents, RecurringInterval,
+};
+
+use chrono::{Month, NaiveDate};
+use std::num::NonZeroU32;
+
+fn one_year_calculator() -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 12000.0,
+        nominal_interest: 2.0,
+        terms: 12,
+        first_installment_month: Month::February,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(NaiveDate::from_ymd(2021, 1, 10), initial)
+}
+
+#[test]
+fn interest_change_increases_interest() {
+    let baseline = one_year_calculator().compute().unwrap();
+
+    let mut calculator = one_year_calculator();
+    let change = LoanInterestChange {
+        nominal_interest: 4.0,
+    };
+    calculator
+        .add_event_interest_change(NaiveDate::from_ymd(2021, 6, 15), change)
+        .unwrap();
+    let summary = calculator.compute().unwrap();
+
+    assert!(summary.total_interest > baseline.total_interest);
+    assert_eq!(summary.end_date, baseline.end_date);
+    assert_eq!(summary.completed_terms, 12);
+}
+
+#[test]
+fn interest_change_must_be_positive() {
+    let mut calculator = one_year_calculator();
+    for nominal_interest in [0.0, -1.0].iter() {
+        let change = LoanInterestChange {
+            nominal_interest: *nominal_interest,
+        };
+        assert!(calculator
+            .add_event_interest_change(NaiveDate::from_ymd(2021, 6, 15), change)
+            .is_err());
+    }
+    assert_eq!(calculator.events().count(), 1);
+
+    // An event changed in place is rejected by the calculation.
+    let change = LoanInterestChange {
+        nominal_interest: 4.0,
+    };
+    calculator
+        .add_event_interest_change(NaiveDate::from_ymd(2021, 6, 15), change)
+        .unwrap();
+    if let (_, LoanEvent::InterestChange(change)) = calculator.event_index(1) {
+        change.nominal_interest = 0.0;
+    }
+    assert!(calculator.compute().is_err());
+}
+
+#[test]
+fn refinance_increases_loan() {
+    let mut calculator = one_year_calculator();
+    let refinance = LoanRefinance {
+        loan_increase: 6000.0,
+        administration_fee: 500.0,
+    };
+    calculator
+        .add_event_refinance(NaiveDate::from_ymd(2021, 6, 15), refinance)
+        .unwrap();
+    let summary = calculator.compute().unwrap();
+
+    assert_eq!(summary.total_loan, 18000.0);
+    assert_eq!(summary.total_fee, 500.0);
+    assert_eq!(summary.end_date, NaiveDate::from_ymd(2022, 1, 1));
+    assert!((summary.total_repayment_installment - 18500.0).abs() < 1e-6);
+}
+
+#[test]
+fn repayment_freeze_postpones_end_date() {
+    let mut calculator = one_year_calculator();
+    let freeze = LoanRepaymentFreeze {
+        count: NonZeroU32::new(3).unwrap(),
+    };
+    calculator
+        .add_event_repayment_freeze(NaiveDate::from_ymd(2021, 3, 15), freeze)
+        .unwrap();
+    let summary = calculator.compute().unwrap();
+
+    assert_eq!(summary.end_date, NaiveDate::from_ymd(2022, 4, 1));
+    assert_eq!(summary.completed_terms, 12);
+    assert!((summary.total_repayment_installment - 12000.0).abs() < 1e-6);
+
+    let ledger = calculator.ledger().unwrap();
+    let interest_only = ledger
+        .iter()
+        .filter(|d| d.interest_installment > 0.0 && d.repayment_installment == 0.0)
+        .count();
+    assert_eq!(interest_only, 3);
+}
+
+#[test]
+fn recurring_extra_installments() {
+    let mut calculator = one_year_calculator();
+    let extra = LoanRecurringExtraInstallments {
+        amount: 500.0,
+        count: NonZeroU32::new(4).unwrap(),
+        recurring_interval: RecurringInterval::Monthly,
+        policy: None,
+        prepayment_fee: None,
+    };
+    calculator
+        .add_event_extra_recurring(NaiveDate::from_ymd(2021, 2, 20), extra)
+        .unwrap();
+    let summary = calculator.compute().unwrap();
+
+    assert_eq!(summary.total_extra_installment, 2000.0);
+    assert!(summary.end_date < NaiveDate::from_ymd(2022, 1, 1));
+}
+
+#[test]
+fn bank_transfer_cancels_extra_installments() {
+    let mut calculator = one_year_calculator();
+    let extra = LoanRecurringExtraInstallments {
+        amount: 500.0,
+        count: NonZeroU32::new(6).unwrap(),
+        recurring_interval: RecurringInterval::Monthly,
+        policy: None,
+        prepayment_fee: None,
+    };
+    calculator
+        .add_event_extra_recurring(NaiveDate::from_ymd(2021, 2, 20), extra)
+        .unwrap();
+    let transfer = LoanTransfer {
+        administration_fee: 250.0,
+        prepayment_fee: None,
+    };
+    calculator
+        .add_event_bank_transfer(NaiveDate::from_ymd(2021, 4, 10), transfer)
+        .unwrap();
+    let summary = calculator.compute().unwrap();
+
+    // Extra installments on 2021-02-20 and 2021-03-20 are payed before the transfer.
+    assert_eq!(summary.total_extra_installment, 1000.0);
+    assert_eq!(summary.total_fee, 250.0);
+}
+
+#[test]
+fn event_before_disbursement_is_rejected() {
+    let mut calculator = one_year_calculator();
+    let extra = LoanExtraInstallment {
+        amount: 100.0,
+        policy: None,
+        prepayment_fee: None,
+    };
+
+    let res = calculator.add_event_extra_single(NaiveDate::from_ymd(2021, 1, 10), extra);
+    assert_eq!(
+        "event date must be after the disbursement date",
+        &res.unwrap_err()
+    );
+}
+
+#[test]
+fn events_are_ordered_by_date() {
+    let mut calculator = one_year_calculator();
+    calculator
+        .add_event_interest_change(
+            NaiveDate::from_ymd(2021, 8, 1),
+            LoanInterestChange {
+                nominal_interest: 3.0,
+            },
+        )
+        .unwrap();
+    calculator
+        .add_event_extra_single(
+            NaiveDate::from_ymd(2021, 3, 1),
+            LoanExtraInstallment {
+                amount: 100.0,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+
+    let dates: Vec<NaiveDate> = calculator.events().map(|(date, _)| date).collect();
+    assert_eq!(
+        dates,
+        vec![
+            NaiveDate::from_ymd(2021, 1, 10),
+            NaiveDate::from_ymd(2021, 3, 1),
+            NaiveDate::from_ymd(2021, 8, 1),
+        ]
+    );
+
+    // Move the interest change prior to the extra installment.
+    calculator
+        .change_event_date(2, NaiveDate::from_ymd(2021, 2, 1))
+        .unwrap();
+    match calculator.event(1) {
+        (date, LoanEvent::InterestChange(_)) => {
+            assert_eq!(date, NaiveDate::from_ymd(2021, 2, 1))
+        }
+        _ => panic!("expected interest change"),
+    }
+
+    // The disbursement cannot be moved past other events.
+    assert!(calculator
+        .change_event_date(0, NaiveDate::from_ymd(2021, 2, 1))
+        .is_err());
+    assert!(calculator.remove_event(0).is_err());
+
+    match calculator.remove_event(2) {
+        Ok(LoanEvent::Extra(extra)) => assert_eq!(extra.amount, 100.0),
+        _ => panic!("expected extra installment"),
+    }
+    assert_eq!(calculator.events().count(), 2);
+}
+
+#[test]
+fn refinance_recomputes_term_payment() {
+    let mut calculator = one_year_calculator();
+    let refinance_date = NaiveDate::from_ymd(2021, 6, 15);
+    calculator
+        .add_event_refinance(
+            refinance_date,
+            LoanRefinance {
+                loan_increase: 6000.0,
+                administration_fee: 500.0,
+            },
+        )
+        .unwrap();
+    let ledger = calculator.ledger().unwrap();
+
+    // The increase and the administration fee are added to the outstanding loan.
+    let position = ledger
+        .iter()
+        .position(|d| d.date == refinance_date)
+        .unwrap();
+    let (before, refinanced) = (&ledger[position - 1], &ledger[position]);
+    assert_eq!(refinanced.disbursed, 6000.0);
+    assert_eq!(refinanced.fee, 500.0);
+    assert!((refinanced.outstanding_loan - before.outstanding_loan - 6500.0).abs() < 1e-9);
+    assert!(refinanced
+        .notable_events
+        .iter()
+        .any(|e| matches!(e, NotableEvents::Refinance(amount) if *amount == 6000.0)));
+
+    // The remaining terms repay the larger loan by the same end date.
+    let terms: Vec<f64> = ledger
+        .iter()
+        .filter(|d| d.repayment_installment > 0.0)
+        .map(|d| d.repayment_installment + d.interest_installment)
+        .collect();
+    assert_eq!(terms.len(), 12);
+    assert!(terms[5] > 1.5 * terms[4]);
+}
+
+#[test]
+fn bank_transfer_settles_accrued_interest() {
+    let mut calculator = one_year_calculator();
+    let transfer_date = NaiveDate::from_ymd(2021, 4, 10);
+    calculator
+        .add_event_bank_transfer(
+            transfer_date,
+            LoanTransfer {
+                administration_fee: 250.0,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    let ledger = calculator.ledger().unwrap();
+
+    // The interest accrued since the last installment is payed to the previous bank,
+    // and the administration fee of the new bank is added to the outstanding loan.
+    let position = ledger.iter().position(|d| d.date == transfer_date).unwrap();
+    let (before, transferred) = (&ledger[position - 1], &ledger[position]);
+    assert!(transferred.interest_installment > 0.0);
+    assert_eq!(transferred.repayment_installment, 0.0);
+    assert_eq!(transferred.fee, 250.0);
+    assert!((transferred.outstanding_loan - before.outstanding_loan - 250.0).abs() < 1e-9);
+    assert!(transferred.notable_events.iter().any(|e| matches!(
+        e,
+        NotableEvents::BankTransfer(outstanding) if *outstanding == transferred.outstanding_loan
+    )));
+
+    // The next installment only carries the interest accrued with the new bank.
+    let next = ledger[position..]
+        .iter()
+        .find(|d| d.repayment_installment > 0.0)
+        .unwrap();
+    let regular = ledger[..position]
+        .iter()
+        .rev()
+        .find(|d| d.repayment_installment > 0.0)
+        .unwrap();
+    assert!(next.interest_installment < regular.interest_installment);
+}
+
+#[test]
+fn edited_events_are_recomputed() {
+    let mut calculator = one_year_calculator();
+    calculator
+        .add_event_interest_change(
+            NaiveDate::from_ymd(2021, 5, 1),
+            LoanInterestChange {
+                nominal_interest: 3.0,
+            },
+        )
+        .unwrap();
+    calculator.compute().unwrap();
+
+    // Editing the event through its index gives the result of the edited event.
+    if let (_, LoanEvent::InterestChange(change)) = calculator.event_index(1) {
+        change.nominal_interest = 5.0;
+    }
+    let mut expected = one_year_calculator();
+    expected
+        .add_event_interest_change(
+            NaiveDate::from_ymd(2021, 5, 1),
+            LoanInterestChange {
+                nominal_interest: 5.0,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        calculator.compute().unwrap().total_interest,
+        expected.compute().unwrap().total_interest
+    );
+
+    // Moving the event gives the result of the event on its new date.
+    calculator
+        .change_event_date(1, NaiveDate::from_ymd(2021, 9, 1))
+        .unwrap();
+    let mut expected = one_year_calculator();
+    expected
+        .add_event_interest_change(
+            NaiveDate::from_ymd(2021, 9, 1),
+            LoanInterestChange {
+                nominal_interest: 5.0,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        calculator.compute().unwrap().total_interest,
+        expected.compute().unwrap().total_interest
+    );
+
+    // Removing the event gives the result of the loan without it.
+    calculator.remove_event(1).unwrap();
+    assert_eq!(
+        calculator.compute().unwrap().total_interest,
+        one_year_calculator().compute().unwrap().total_interest
+    );
+}
@@ -16,12 +16,17 @@ fn interactive_calculator_extra_installment() {
         terms_per_year: TermsPerYear::Twelve,
         due_within_month: MonthlyDueDate::First,
         first_installment_month: Month::February,
+        ..LoanInitialization::default()
     };
 
     let loan_start_date = NaiveDate::from_ymd(2021, 1, 10);
-    let mut calculator = InteractiveCalculator::new(loan_start_date.clone(), initial);
+    let mut calculator = InteractiveCalculator::new(loan_start_date, initial);
 
-    let extra_event = LoanExtraInstallment { amount: 100.0 };
+    let extra_event = LoanExtraInstallment {
+        amount: 100.0,
+        policy: None,
+        prepayment_fee: None,
+    };
     let extra_date = NaiveDate::from_ymd(2021, 2, 20);
     calculator
         .add_event_extra_single(extra_date, extra_event)
@@ -35,3 +40,34 @@ fn interactive_calculator_extra_installment() {
     assert_eq!(summary.total_extra_installment, 100.0);
     assert_eq!(summary.end_date, NaiveDate::from_ymd(2021, 12, 1));
 }
+
+#[test]
+fn interactive_calculator_interest_saved_by_extra() {
+    let initial = LoanInitialization {
+        loan: 1000.0,
+        nominal_interest: 1.0,
+        administration_fee: 0.0,
+        installment_fee: 0.0,
+        terms: 12,
+        terms_per_year: TermsPerYear::Twelve,
+        due_within_month: MonthlyDueDate::First,
+        first_installment_month: Month::February,
+        ..LoanInitialization::default()
+    };
+
+    let loan_start_date = NaiveDate::from_ymd(2021, 1, 10);
+    let mut calculator = InteractiveCalculator::new(loan_start_date, initial);
+
+    let extra_event = LoanExtraInstallment {
+        amount: 100.0,
+        policy: None,
+        prepayment_fee: None,
+    };
+    calculator
+        .add_event_extra_single(NaiveDate::from_ymd(2021, 2, 20), extra_event)
+        .unwrap();
+
+    let saved = calculator.interest_saved_by_extra().unwrap();
+    assert!(saved > 0.0);
+    assert!(saved < 1.0);
+}
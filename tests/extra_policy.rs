@@ -0,0 +1,157 @@
+use interest_calculator::{
+    Amortisation, ExtraInstallmentPolicy, InteractiveCalculator, LoanExtraInstallment,
+    LoanInitialization, ScheduleEntry,
+};
+
+use chrono::{Month, NaiveDate};
+
+fn loan(amortisation: Amortisation, extra_policy: ExtraInstallmentPolicy) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 120000.0,
+        nominal_interest: 4.0,
+        terms: 60,
+        first_installment_month: Month::February,
+        amortisation,
+        extra_policy,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(NaiveDate::from_ymd(2021, 1, 1), initial)
+}
+
+fn with_extra(
+    mut calculator: InteractiveCalculator,
+    policy: Option<ExtraInstallmentPolicy>,
+) -> InteractiveCalculator {
+    calculator
+        .add_event_extra_single(
+            NaiveDate::from_ymd(2022, 6, 15),
+            LoanExtraInstallment {
+                amount: 30000.0,
+                policy,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    calculator
+}
+
+/// The principal and interest payed on each scheduled repayment installment.
+fn repayments(calculator: &InteractiveCalculator) -> Vec<(NaiveDate, f64, f64)> {
+    ScheduleEntry::from_ledger(&calculator.ledger().unwrap())
+        .iter()
+        .filter(|e| e.principal > 0.0)
+        .map(|e| (e.date, e.principal, e.interest))
+        .collect()
+}
+
+#[test]
+fn annuity_shorten_term_keeps_payment() {
+    let plain = loan(Amortisation::Annuity, ExtraInstallmentPolicy::ShortenTerm);
+    let calculator = with_extra(plain.clone(), None);
+    let summary = calculator.compute().unwrap();
+
+    assert!(summary.end_date < plain.compute().unwrap().end_date);
+    assert!(summary.completed_terms < 60);
+
+    // Every regular term payment is the same as before the extra installment.
+    let payments = repayments(&calculator);
+    for (_, principal, interest) in payments[..payments.len() - 1].iter() {
+        assert!((principal + interest - summary.term_payment).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn annuity_lower_payment_keeps_term() {
+    let plain = loan(Amortisation::Annuity, ExtraInstallmentPolicy::LowerPayment);
+    let calculator = with_extra(plain.clone(), None);
+    let summary = calculator.compute().unwrap();
+
+    assert_eq!(summary.end_date, plain.compute().unwrap().end_date);
+    assert_eq!(summary.completed_terms, 60);
+
+    // The term payment is lowered after the extra installment.
+    let extra_date = NaiveDate::from_ymd(2022, 6, 15);
+    let payments = repayments(&calculator);
+    let (before, after): (Vec<_>, Vec<_>) = payments.iter().partition(|(d, _, _)| *d < extra_date);
+    let payment = |(_, principal, interest): &&(NaiveDate, f64, f64)| principal + interest;
+    let lowered = payment(&after[0]);
+    assert!(lowered < payment(&before[0]) - 500.0);
+    // The last term settles the remaining loan.
+    assert!(after[..after.len() - 1]
+        .iter()
+        .all(|p| (payment(p) - lowered).abs() < 1e-6));
+
+    // Keeping the payment saves more interest than keeping the term.
+    let shorten = with_extra(
+        loan(Amortisation::Annuity, ExtraInstallmentPolicy::ShortenTerm),
+        None,
+    );
+    assert!(shorten.compute().unwrap().total_interest < summary.total_interest);
+}
+
+#[test]
+fn serial_repays_fixed_principal() {
+    let calculator = loan(Amortisation::Serial, ExtraInstallmentPolicy::ShortenTerm);
+    let summary = calculator.compute().unwrap();
+
+    assert_eq!(summary.completed_terms, 60);
+    assert!((summary.total_repayment_installment - 120000.0).abs() < 1e-6);
+
+    // The same principal every term, while the interest and thereby the payment decreases.
+    let payments = repayments(&calculator);
+    assert!(payments.iter().all(|(_, p, _)| (p - 2000.0).abs() < 1e-6));
+    assert!(payments.first().unwrap().2 > payments.last().unwrap().2);
+
+    // A serial loan pays less interest than the equivalent annuity loan.
+    let annuity = loan(Amortisation::Annuity, ExtraInstallmentPolicy::ShortenTerm);
+    assert!(summary.total_interest < annuity.compute().unwrap().total_interest);
+}
+
+#[test]
+fn serial_shorten_term_keeps_principal() {
+    let calculator = with_extra(
+        loan(Amortisation::Serial, ExtraInstallmentPolicy::ShortenTerm),
+        None,
+    );
+    let summary = calculator.compute().unwrap();
+
+    // The extra installment covers 15 terms of principal.
+    assert_eq!(summary.completed_terms, 45);
+    let payments = repayments(&calculator);
+    assert!(payments.iter().all(|(_, p, _)| (p - 2000.0).abs() < 1e-6));
+}
+
+#[test]
+fn serial_lower_payment_keeps_term() {
+    let plain = loan(Amortisation::Serial, ExtraInstallmentPolicy::LowerPayment);
+    let calculator = with_extra(plain.clone(), None);
+    let summary = calculator.compute().unwrap();
+
+    assert_eq!(summary.completed_terms, 60);
+    assert_eq!(summary.end_date, plain.compute().unwrap().end_date);
+
+    // The remaining principal is spread evenly over the remaining terms.
+    let payments = repayments(&calculator);
+    let extra_date = NaiveDate::from_ymd(2022, 6, 15);
+    let lowered = payments.iter().find(|(d, _, _)| *d > extra_date).unwrap().1;
+    assert!(lowered < 2000.0);
+    assert!(payments
+        .iter()
+        .filter(|(d, _, _)| *d > extra_date)
+        .all(|(_, p, _)| (p - lowered).abs() < 1e-6));
+}
+
+#[test]
+fn event_policy_overrides_loan_policy() {
+    let shorten = loan(Amortisation::Annuity, ExtraInstallmentPolicy::ShortenTerm);
+    let lower = loan(Amortisation::Annuity, ExtraInstallmentPolicy::LowerPayment);
+
+    let overridden = with_extra(shorten, Some(ExtraInstallmentPolicy::LowerPayment));
+    let expected = with_extra(lower, None);
+
+    let overridden = overridden.compute().unwrap();
+    let expected = expected.compute().unwrap();
+    assert_eq!(overridden.end_date, expected.end_date);
+    assert_eq!(overridden.total_interest, expected.total_interest);
+}
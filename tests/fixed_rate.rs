@@ -0,0 +1,134 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    FloatingRate, IndexSeries, IndexedRate, InteractiveCalculator, LoanInitialization,
+    LoanInterestChange, MonthlyDueDate, RecurringInterval, ScheduleEntry,
+};
+
+use chrono::{Month, NaiveDate};
+
+fn fixed_rate_mortgage(
+    fixed_until: Option<NaiveDate>,
+    floating_rate: Option<FloatingRate>,
+) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 2000000.0,
+        nominal_interest: 2.0,
+        terms: 240,
+        due_within_month: MonthlyDueDate::Date(20),
+        first_installment_month: Month::February,
+        fixed_until,
+        floating_rate,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(NaiveDate::from_ymd(2021, 1, 10), initial)
+}
+
+#[test]
+fn fixed_rate_reverts_to_rate_path() {
+    let path = FloatingRate::Path(vec![(date(2020, 1, 1), 4.0), (date(2027, 1, 1), 5.0)]);
+    let calculator = fixed_rate_mortgage(Some(date(2025, 12, 31)), Some(path));
+    let summary = calculator.compute().unwrap();
+
+    // The payment shock after the fixed-rate period.
+    let after = summary.term_payment_after_fixed_rate.unwrap();
+    assert!(after > summary.term_payment + 1000.0);
+
+    // The rate path is applied as interest changes, where the rate effective before
+    // the end of the fixed-rate period is the rate the loan reverts to.
+    let schedule = ScheduleEntry::from_ledger(&calculator.ledger().unwrap());
+    let ends: Vec<_> = schedule
+        .iter()
+        .filter_map(|e| e.fixed_rate_end().map(|rate| (e.date, rate)))
+        .collect();
+    assert_eq!(ends, vec![(date(2026, 1, 1), 4.0)]);
+
+    // The same loan with hand written interest changes.
+    let mut manual = fixed_rate_mortgage(None, None);
+    for (date, nominal_interest) in [(date(2026, 1, 1), 4.0), (date(2027, 1, 1), 5.0)] {
+        manual
+            .add_event_interest_change(date, LoanInterestChange { nominal_interest })
+            .unwrap();
+    }
+    let expected = manual.compute().unwrap();
+    assert_eq!(summary.total_interest, expected.total_interest);
+    assert_eq!(summary.end_date, expected.end_date);
+}
+
+#[test]
+fn fixed_rate_reverts_to_margin_over_index() {
+    let index = IndexSeries::new(vec![(date(2025, 6, 1), 2.5), (date(2026, 6, 1), 3.0)]);
+    let margin = FloatingRate::Indexed(IndexedRate {
+        index,
+        margin: 1.5,
+        reset: RecurringInterval::Monthly,
+        lookback_days: 0,
+    });
+    // The rate observed on the first monthly reset after the index changes.
+    let path = FloatingRate::Path(vec![(date(2025, 6, 1), 4.0), (date(2026, 6, 1), 4.5)]);
+
+    let margin = fixed_rate_mortgage(Some(date(2025, 12, 31)), Some(margin))
+        .compute()
+        .unwrap();
+    let path = fixed_rate_mortgage(Some(date(2025, 12, 31)), Some(path))
+        .compute()
+        .unwrap();
+    assert_eq!(margin.total_interest, path.total_interest);
+}
+
+#[test]
+fn floating_rate_without_fixed_period() {
+    // The floating rate applies from the disbursement.
+    let path = FloatingRate::Path(vec![(date(2021, 1, 1), 3.0)]);
+    let floating = fixed_rate_mortgage(None, Some(path)).compute().unwrap();
+    assert!(floating.term_payment_after_fixed_rate.is_none());
+
+    let mut fixed = fixed_rate_mortgage(None, None);
+    if let (_, interest_calculator::LoanEvent::Initial(init)) = fixed.event_index(0) {
+        init.nominal_interest = 3.0;
+    }
+    assert_eq!(
+        floating.total_interest,
+        fixed.compute().unwrap().total_interest
+    );
+}
+
+#[test]
+fn interest_change_event_takes_precedence() {
+    let path = FloatingRate::Path(vec![(date(2026, 1, 1), 4.0)]);
+    let mut calculator = fixed_rate_mortgage(Some(date(2025, 12, 31)), Some(path));
+    calculator
+        .add_event_interest_change(
+            date(2026, 1, 1),
+            LoanInterestChange {
+                nominal_interest: 3.0,
+            },
+        )
+        .unwrap();
+
+    let ledger = calculator.ledger().unwrap();
+    let reset = ledger.iter().find(|d| d.date == date(2026, 1, 1)).unwrap();
+    let schedule = ScheduleEntry::from_ledger(std::slice::from_ref(reset));
+    assert_eq!(schedule[0].fixed_rate_end(), Some(3.0));
+}
+
+#[test]
+fn fixed_rate_validation() {
+    // A fixed-rate period must be followed by a floating rate.
+    assert!(fixed_rate_mortgage(Some(date(2025, 12, 31)), None)
+        .compute()
+        .is_err());
+
+    // The fixed-rate period can not end before the disbursement.
+    let path = FloatingRate::Path(vec![(date(2026, 1, 1), 4.0)]);
+    assert!(fixed_rate_mortgage(Some(date(2020, 12, 31)), Some(path))
+        .compute()
+        .is_err());
+
+    let path = FloatingRate::Path(vec![(date(2026, 1, 1), -1.0)]);
+    assert!(fixed_rate_mortgage(Some(date(2025, 12, 31)), Some(path))
+        .compute()
+        .is_err());
+}
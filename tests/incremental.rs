@@ -0,0 +1,148 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    BusinessDayConvention, InteractiveCalculator, LoanEvent, LoanInitialization,
+    LoanInterestChange, LoanRecurringExtraInstallments, LoanRefinance, LoanRepaymentFreeze,
+    MonthlyDueDate, RateLimits, RecurringInterval, WeekendCalendar,
+};
+
+use chrono::Month;
+use std::num::NonZeroU32;
+
+fn mortgage() -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 3000000.0,
+        nominal_interest: 3.5,
+        administration_fee: 1500.0,
+        installment_fee: 50.0,
+        terms: 360,
+        due_within_month: MonthlyDueDate::Date(15),
+        first_installment_month: Month::February,
+        business_day_convention: BusinessDayConvention::Following,
+        rate_limits: RateLimits {
+            cap: Some(6.0),
+            ..RateLimits::default()
+        },
+        ..LoanInitialization::default()
+    };
+
+    let mut calculator = InteractiveCalculator::new(date(2021, 1, 5), initial);
+    for (year, nominal_interest) in [(2023, 4.5), (2026, 7.0), (2031, 3.0)].iter() {
+        calculator
+            .add_event_interest_change(
+                date(*year, 3, 1),
+                LoanInterestChange {
+                    nominal_interest: *nominal_interest,
+                },
+            )
+            .unwrap();
+    }
+    calculator
+        .add_event_extra_recurring(
+            date(2022, 6, 20),
+            LoanRecurringExtraInstallments {
+                amount: 2000.0,
+                count: NonZeroU32::new(48).unwrap(),
+                recurring_interval: RecurringInterval::Monthly,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    calculator
+}
+
+/// Assert that the calculator computes the same results as a calculation from scratch.
+fn assert_same_as_full(calculator: &InteractiveCalculator) {
+    // A cloned calculator does not keep the checkpoints of previous calculations.
+    let full = calculator.clone();
+    assert_eq!(
+        format!("{:?}", calculator.compute()),
+        format!("{:?}", full.compute())
+    );
+    assert_eq!(
+        format!("{:?}", calculator.ledger()),
+        format!("{:?}", full.ledger())
+    );
+}
+
+#[test]
+fn edits_resume_from_checkpoints() {
+    let mut calculator = mortgage();
+    assert_same_as_full(&calculator);
+
+    // Add, edit, move and remove events after the loan has been computed.
+    calculator
+        .add_event_refinance(
+            date(2028, 9, 2),
+            LoanRefinance {
+                loan_increase: 500000.0,
+                administration_fee: 2000.0,
+            },
+        )
+        .unwrap();
+    assert_same_as_full(&calculator);
+
+    match calculator.event_index(2) {
+        (_, LoanEvent::InterestChange(change)) => change.nominal_interest = 5.25,
+        (_, event) => panic!("unexpected event {:?}", event),
+    }
+    assert_same_as_full(&calculator);
+
+    calculator.change_event_date(4, date(2021, 11, 30)).unwrap();
+    assert_same_as_full(&calculator);
+
+    calculator
+        .add_event_repayment_freeze(
+            date(2024, 1, 1),
+            LoanRepaymentFreeze {
+                count: NonZeroU32::new(6).unwrap(),
+            },
+        )
+        .unwrap();
+    assert_same_as_full(&calculator);
+
+    calculator.remove_event(3).unwrap();
+    assert_same_as_full(&calculator);
+
+    // Changes to the initial loan event affect the entire calculation.
+    match calculator.event_index(0) {
+        (_, LoanEvent::Initial(initial)) => initial.terms = 240,
+        (_, event) => panic!("unexpected event {:?}", event),
+    }
+    assert_same_as_full(&calculator);
+
+    calculator.change_event_date(0, date(2020, 12, 1)).unwrap();
+    assert_same_as_full(&calculator);
+
+    calculator.set_holiday_calendar(WeekendCalendar);
+    assert_same_as_full(&calculator);
+}
+
+#[test]
+fn snapshots_after_edits() {
+    let mut calculator = mortgage();
+    let dates = [date(2021, 1, 5), date(2025, 5, 1), date(2040, 12, 31)];
+    calculator.compute().unwrap();
+
+    calculator
+        .add_event_interest_change(
+            date(2030, 1, 1),
+            LoanInterestChange {
+                nominal_interest: 2.0,
+            },
+        )
+        .unwrap();
+    assert_eq!(
+        calculator.snapshots(&dates),
+        calculator.clone().snapshots(&dates)
+    );
+
+    // Snapshots resume from the checkpoints of the complete calculation.
+    calculator.compute().unwrap();
+    assert_eq!(
+        calculator.snapshots(&dates),
+        calculator.clone().snapshots(&dates)
+    );
+}
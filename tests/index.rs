@@ -0,0 +1,115 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    FloatingRate, IndexSeries, IndexedRate, InteractiveCalculator, LoanInitialization,
+    MonthlyDueDate, RecurringInterval,
+};
+
+use chrono::Month;
+
+fn floating_loan(floating_rate: FloatingRate) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 1000000.0,
+        nominal_interest: 2.0,
+        terms: 120,
+        due_within_month: MonthlyDueDate::Date(20),
+        first_installment_month: Month::February,
+        floating_rate: Some(floating_rate),
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(date(2021, 1, 10), initial)
+}
+
+fn indexed(index: &IndexSeries, reset: RecurringInterval, lookback_days: u32) -> FloatingRate {
+    FloatingRate::Indexed(IndexedRate {
+        index: index.clone(),
+        margin: 1.0,
+        reset,
+        lookback_days,
+    })
+}
+
+#[test]
+fn index_from_csv() {
+    let csv = "date, rate\n2021-05-01, 2.0\n2021-01-01, 1.0\n2021-02-15, 1.25\n2021-02-15, 1.5\n";
+    let index = IndexSeries::from_csv(csv.as_bytes()).unwrap();
+
+    // Ordered by date, where the later rate on the same date is kept.
+    assert_eq!(
+        index.rates(),
+        &[
+            (date(2021, 1, 1), 1.0),
+            (date(2021, 2, 15), 1.5),
+            (date(2021, 5, 1), 2.0)
+        ]
+    );
+    assert_eq!(index.rate_on(date(2020, 12, 31)), None);
+    assert_eq!(index.rate_on(date(2021, 2, 14)), Some(1.0));
+    assert_eq!(index.rate_on(date(2021, 2, 15)), Some(1.5));
+    assert_eq!(index.rate_on(date(2030, 1, 1)), Some(2.0));
+
+    assert!(IndexSeries::from_csv("date,rate\n2021-13-01,1.0\n".as_bytes()).is_err());
+    assert!(IndexSeries::from_csv("date,rate\n2021-01-01,high\n".as_bytes()).is_err());
+}
+
+#[test]
+fn index_reset_frequency() {
+    let index = IndexSeries::new(vec![
+        (date(2021, 1, 1), 1.0),
+        (date(2021, 2, 15), 1.5),
+        (date(2021, 5, 1), 2.0),
+    ]);
+
+    // The index is only observed on every quarterly reset from the disbursement.
+    let quarterly = floating_loan(indexed(&index, RecurringInterval::Quarerly, 0));
+    let path = floating_loan(FloatingRate::Path(vec![
+        (date(2021, 1, 10), 2.0),
+        (date(2021, 4, 10), 2.5),
+        (date(2021, 7, 10), 3.0),
+    ]));
+    let quarterly = quarterly.compute().unwrap();
+    assert_eq!(
+        quarterly.total_interest,
+        path.compute().unwrap().total_interest
+    );
+
+    // Monthly resets follow the index sooner, and costs more as the index rises.
+    let monthly = floating_loan(indexed(&index, RecurringInterval::Monthly, 0));
+    let path = floating_loan(FloatingRate::Path(vec![
+        (date(2021, 1, 10), 2.0),
+        (date(2021, 3, 10), 2.5),
+        (date(2021, 5, 10), 3.0),
+    ]));
+    let monthly = monthly.compute().unwrap();
+    assert_eq!(
+        monthly.total_interest,
+        path.compute().unwrap().total_interest
+    );
+    assert!(monthly.total_interest > quarterly.total_interest);
+}
+
+#[test]
+fn index_lookback() {
+    let index = IndexSeries::new(vec![(date(2020, 12, 1), 1.0), (date(2021, 3, 1), 1.5)]);
+
+    // The reset on 2021-03-10 observes the index on 2021-02-08, before it changed.
+    let lookback = floating_loan(indexed(&index, RecurringInterval::Monthly, 30));
+    let path = floating_loan(FloatingRate::Path(vec![
+        (date(2021, 1, 10), 2.0),
+        (date(2021, 4, 10), 2.5),
+    ]));
+    assert_eq!(
+        lookback.compute().unwrap().total_interest,
+        path.compute().unwrap().total_interest
+    );
+
+    // The index must have a rate on the first observation.
+    let index = IndexSeries::new(vec![(date(2021, 1, 1), 1.0)]);
+    assert!(
+        floating_loan(indexed(&index, RecurringInterval::Monthly, 30))
+            .compute()
+            .is_err()
+    );
+}
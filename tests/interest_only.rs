@@ -0,0 +1,71 @@
+use interest_calculator::{
+    InteractiveCalculator, LoanInitialization, MonthlyDueDate, ScheduleEntry,
+};
+
+use chrono::{Month, NaiveDate};
+
+fn mortgage(interest_only_terms: u32) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 1200000.0,
+        nominal_interest: 3.0,
+        installment_fee: 50.0,
+        terms: 120,
+        due_within_month: MonthlyDueDate::Date(20),
+        first_installment_month: Month::February,
+        interest_only_terms,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(NaiveDate::from_ymd(2021, 1, 10), initial)
+}
+
+#[test]
+fn interest_only_terms_precede_amortisation() {
+    let amortised = mortgage(0).compute().unwrap();
+    let summary = mortgage(24).compute().unwrap();
+
+    // Both phases share the total number of terms, and the loan ends on the same date.
+    assert_eq!(summary.interest_only_terms, 24);
+    assert_eq!(summary.planned_terms, 96);
+    assert_eq!(summary.completed_terms, 96);
+    assert_eq!(summary.end_date, amortised.end_date);
+    assert_eq!(
+        summary.first_installment_date,
+        NaiveDate::from_ymd(2023, 2, 20)
+    );
+
+    // The loan is amortised over fewer terms, with more interest payed in total.
+    assert!(summary.term_payment > amortised.term_payment);
+    assert!(summary.total_interest > amortised.total_interest);
+    assert!((summary.total_repayment_installment - 1200000.0).abs() < 1e-6);
+
+    // Every term includes the installment fee.
+    assert!((summary.total_fee - 120.0 * 50.0).abs() < 1e-6);
+}
+
+#[test]
+fn interest_only_installments_pay_interest_and_fee() {
+    let ledger = mortgage(12).ledger().unwrap();
+    let schedule = ScheduleEntry::from_ledger(&ledger);
+
+    let interest_only: Vec<_> = schedule.iter().filter(|e| e.is_interest_only()).collect();
+    assert_eq!(interest_only.len(), 12);
+    for entry in interest_only {
+        assert_eq!(entry.principal, 0.0);
+        assert_eq!(entry.fee, 50.0);
+        assert!((entry.payment - entry.interest - entry.fee).abs() < 1e-6);
+        assert!((entry.outstanding_loan - 1200000.0).abs() < 1e-6);
+    }
+
+    // The amortisation phase follows the interest-only phase.
+    let first_repayment = schedule.iter().position(|e| e.principal > 0.0).unwrap();
+    assert!(schedule[first_repayment..]
+        .iter()
+        .all(|e| !e.is_interest_only()));
+}
+
+#[test]
+fn interest_only_terms_must_be_fewer_than_terms() {
+    assert!(mortgage(120).compute().is_err());
+    assert!(mortgage(119).compute().is_ok());
+}
@@ -0,0 +1,197 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    Engine, InteractiveCalculator, LoanExtraInstallment, LoanInitialization, LoanPayoff,
+    MonthlyCashFlow, MonthlyDueDate, NotableEvents, PrepaymentFee, Scenario, ScheduleEntry,
+    TaxStatement, YearlyResult,
+};
+
+use chrono::{Month, NaiveDate};
+
+fn loan() -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 200000.0,
+        nominal_interest: 4.0,
+        installment_fee: 50.0,
+        terms: 120,
+        due_within_month: MonthlyDueDate::Date(20),
+        first_installment_month: Month::February,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(date(2021, 1, 10), initial)
+}
+
+#[test]
+fn quote_between_installments() {
+    let calculator = loan();
+    let quote = calculator.payoff_quote(date(2022, 3, 5)).unwrap();
+
+    // The principal after the last installment, with the interest accrued since.
+    let mut daily = loan();
+    daily.set_engine(Engine::Daily);
+    let ledger = daily.ledger().unwrap();
+    let last_installment = ledger
+        .iter()
+        .rev()
+        .find(|d| d.date < quote.date && d.repayment_installment > 0.0)
+        .unwrap();
+    let accrued: f64 = ledger
+        .iter()
+        .filter(|d| d.date > last_installment.date && d.date <= quote.date)
+        .map(|d| d.accrued_interest)
+        .sum();
+    assert_eq!(last_installment.date, date(2022, 2, 20));
+    assert!((quote.outstanding_principal - last_installment.outstanding_loan).abs() < 1e-6);
+    assert!((quote.accrued_interest - accrued).abs() < 1e-6);
+    assert_eq!(quote.administration_fee, 0.0);
+    assert_eq!(quote.prepayment_fee, 0.0);
+    assert_eq!(
+        quote.total,
+        quote.outstanding_principal + quote.accrued_interest
+    );
+
+    // Prior events are taken into account, while later events are not.
+    let with_extras = |dates: &[NaiveDate]| {
+        let mut calculator = loan();
+        for extra_date in dates.iter() {
+            calculator
+                .add_event_extra_single(
+                    *extra_date,
+                    LoanExtraInstallment {
+                        amount: 10000.0,
+                        policy: None,
+                        prepayment_fee: None,
+                    },
+                )
+                .unwrap();
+        }
+        calculator.payoff_quote(quote.date).unwrap()
+    };
+    let prior = with_extras(&[date(2021, 6, 1)]);
+    assert!(prior.outstanding_principal < quote.outstanding_principal - 10000.0);
+    assert_eq!(prior, with_extras(&[date(2021, 6, 1), date(2022, 6, 1)]));
+
+    // A quote is only available while the loan is outstanding.
+    assert!(calculator.payoff_quote(date(2021, 1, 10)).is_err());
+    assert!(calculator.payoff_quote(date(2040, 1, 1)).is_err());
+}
+
+#[test]
+fn early_payoff_ends_loan() {
+    let payoff = LoanPayoff {
+        administration_fee: 750.0,
+        prepayment_fee: Some(PrepaymentFee::Percentage(1.0)),
+    };
+    let mut calculator = loan();
+    calculator
+        .add_event_early_payoff(date(2024, 8, 20), payoff)
+        .unwrap();
+
+    // The payoff follows the installment due on the same date.
+    let quote = calculator.payoff_quote(date(2024, 8, 20)).unwrap();
+    assert_eq!(quote.accrued_interest, 0.0);
+    assert_eq!(quote.administration_fee, 750.0);
+    assert!((quote.prepayment_fee - quote.outstanding_principal / 100.0).abs() < 1e-6);
+
+    let summary = calculator.compute().unwrap();
+    assert_eq!(summary.end_date, date(2024, 8, 20));
+    assert_eq!(
+        summary.total_payoff_installment,
+        quote.outstanding_principal
+    );
+    assert!((summary.total_prepayment_fee - quote.prepayment_fee).abs() < 1e-6);
+    assert!(
+        (summary.total_repayment_installment + summary.total_payoff_installment - 200000.0).abs()
+            < 1e-6
+    );
+
+    let ledger = calculator.ledger().unwrap();
+    let last = ledger.last().unwrap();
+    assert!(last.outstanding_loan.abs() < 1e-6);
+    assert!(last
+        .notable_events
+        .iter()
+        .any(|e| matches!(e, NotableEvents::EarlyPayoff(q) if *q == quote)));
+
+    // Quoting an earlier date applies the fees of the planned payoff.
+    let earlier = calculator.payoff_quote(date(2023, 1, 5)).unwrap();
+    assert_eq!(earlier.administration_fee, 750.0);
+    assert!(earlier.outstanding_principal > quote.outstanding_principal);
+
+    // The payoff event is kept in a scenario.
+    let scenario = Scenario::from_json(
+        &Scenario::from_calculator("payoff", &calculator)
+            .to_json()
+            .unwrap(),
+    )
+    .unwrap();
+    let restored = scenario.to_calculator().unwrap().compute().unwrap();
+    assert_eq!(restored.end_date, summary.end_date);
+    assert_eq!(restored.total_cost, summary.total_cost);
+}
+
+#[test]
+fn payoff_right_after_disbursement() {
+    let mut calculator = loan();
+    calculator
+        .add_event_early_payoff(
+            date(2021, 1, 11),
+            LoanPayoff {
+                administration_fee: 5000.0,
+                prepayment_fee: Some(PrepaymentFee::Flat(1000.0)),
+            },
+        )
+        .unwrap();
+
+    // The fees payed the day after disbursement put the effective rate out of range,
+    // while the totals are still computed.
+    let summary = calculator.compute().unwrap();
+    assert_eq!(summary.end_date, date(2021, 1, 11));
+    assert_eq!(summary.completed_terms, 0);
+    assert!((summary.total_fee - 6000.0).abs() < 1e-6);
+    assert_eq!(summary.effective_annual_rate, None);
+}
+
+#[test]
+fn payoff_fees_in_reports() {
+    let mut calculator = loan();
+    calculator
+        .add_event_early_payoff(
+            date(2023, 5, 12),
+            LoanPayoff {
+                administration_fee: 200.0,
+                prepayment_fee: Some(PrepaymentFee::Flat(300.0)),
+            },
+        )
+        .unwrap();
+    let summary = calculator.compute().unwrap();
+    let ledger = calculator.ledger().unwrap();
+
+    let yearly = YearlyResult::from_ledger(&ledger);
+    let payed: f64 = yearly.iter().map(|y| y.total_payed()).sum();
+    assert!((payed - summary.total_cost).abs() < 1e-6);
+    let fee: f64 = yearly.iter().map(|y| y.fee).sum();
+    assert!((fee - summary.total_fee).abs() < 1e-6);
+
+    let monthly = MonthlyCashFlow::from_ledger(&ledger);
+    let payed: f64 = monthly.iter().map(|m| m.total_payed()).sum();
+    assert!((payed - summary.total_cost).abs() < 1e-6);
+
+    // The payoff quote is split into its portions.
+    let schedule = ScheduleEntry::from_ledger(&ledger);
+    let payoff = schedule.last().unwrap();
+    assert_eq!(payoff.date, date(2023, 5, 12));
+    assert!((payoff.fee - 500.0).abs() < 1e-6);
+    let portions = payoff.interest + payoff.principal + payoff.fee + payoff.extra;
+    assert!((payoff.payment - portions).abs() < 1e-6);
+
+    let statements = TaxStatement::from_ledger(&ledger);
+    let fee: f64 = statements
+        .iter()
+        .flat_map(|s| s.lenders.iter())
+        .map(|l| l.fee)
+        .sum();
+    assert!((fee - summary.total_fee).abs() < 1e-6);
+}
@@ -0,0 +1,117 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    InteractiveCalculator, LoanInitialization, MonthlyDueDate, Portfolio, Scenario,
+};
+
+use chrono::{Datelike, Month, NaiveDate};
+use num_traits::FromPrimitive;
+
+fn loan(
+    disbursement: NaiveDate,
+    loan: f64,
+    nominal_interest: f64,
+    terms: u32,
+) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan,
+        nominal_interest,
+        terms,
+        due_within_month: MonthlyDueDate::Date(20),
+        first_installment_month: Month::from_u32(disbursement.month() % 12 + 1).unwrap(),
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(disbursement, initial)
+}
+
+fn portfolio() -> Portfolio {
+    let mut portfolio = Portfolio::new();
+    portfolio.add("mortgage", loan(date(2020, 3, 2), 2000000.0, 3.0, 300));
+    portfolio.add("car", loan(date(2021, 6, 10), 300000.0, 6.0, 60));
+    portfolio.add("matured", loan(date(2019, 1, 10), 50000.0, 8.0, 24));
+    portfolio.add("future", loan(date(2023, 1, 10), 100000.0, 4.0, 36));
+    portfolio
+}
+
+#[test]
+fn portfolio_totals() {
+    let portfolio = portfolio();
+    let as_of = date(2022, 1, 1);
+    let result = portfolio.compute(as_of).unwrap();
+    assert_eq!(result.loans.len(), 4);
+    assert_eq!(result.loans[1].name, "car");
+
+    // Only the disbursed loans are outstanding.
+    let outstanding: Vec<f64> = result.loans.iter().map(|l| l.outstanding()).collect();
+    assert!(outstanding[0] > 0.0 && outstanding[1] > 0.0);
+    assert_eq!(outstanding[2], 0.0);
+    assert!(result.loans[3].snapshot.is_none());
+    assert_eq!(result.total_outstanding, outstanding.iter().sum::<f64>());
+
+    let weighted = (outstanding[0] * 3.0 + outstanding[1] * 6.0) / result.total_outstanding;
+    assert!((result.weighted_average_rate - weighted).abs() < 1e-9);
+
+    // The outstanding loans by the year they mature.
+    assert_eq!(
+        result.maturity_profile.keys().copied().collect::<Vec<_>>(),
+        vec![2026, 2045]
+    );
+    assert_eq!(result.maturity_profile[&2026], outstanding[1]);
+
+    // The interest income of every year sums the interest of every loan.
+    let income_2021: f64 = result
+        .loans
+        .iter()
+        .flat_map(|l| l.yearly.iter())
+        .filter(|y| y.year == 2021)
+        .map(|y| y.interest)
+        .sum();
+    assert!((result.interest_income[&2021] - income_2021).abs() < 1e-6);
+    assert_eq!(*result.interest_income.keys().next().unwrap(), 2019);
+    let total_interest: f64 = result.loans.iter().map(|l| l.summary.total_interest).sum();
+    assert!((result.interest_income.values().sum::<f64>() - total_interest).abs() < 1e-6);
+
+    // Nothing is outstanding before the first disbursement.
+    let empty = portfolio.compute(date(2018, 1, 1)).unwrap();
+    assert_eq!(empty.total_outstanding, 0.0);
+    assert_eq!(empty.weighted_average_rate, 0.0);
+    assert!(empty.maturity_profile.is_empty());
+}
+
+#[test]
+fn portfolio_from_files() {
+    let csv = "\
+name,date,loan,nominal_interest,terms,terms_per_year,installment_fee,amortisation
+first, 2021-01-10, 500000, 3.5, 120, 12, 50, serial
+second, 2021-05-01, 250000, 4.0, 40, 4, , \n";
+    let portfolio = Portfolio::from_csv(csv.as_bytes()).unwrap();
+    assert_eq!(portfolio.len(), 2);
+    let result = portfolio.compute(date(2021, 12, 31)).unwrap();
+    assert_eq!(
+        result.loans[0].summary.first_installment_date,
+        date(2021, 2, 20)
+    );
+    assert_eq!(result.loans[0].summary.completed_terms, 120);
+    assert_eq!(result.loans[1].summary.completed_terms, 40);
+    assert_eq!(result.loans[1].summary.end_date.year(), 2031);
+
+    let invalid =
+        "name,date,loan,nominal_interest,terms,terms_per_year\nodd,2021-01-10,1000,3,10,5\n";
+    assert!(Portfolio::from_csv(invalid.as_bytes()).is_err());
+
+    // A JSON portfolio is a list of scenarios, named by the scenarios.
+    let scenarios: Vec<Scenario> = portfolio
+        .loans()
+        .map(|(name, calculator)| Scenario::from_calculator(name, calculator))
+        .collect();
+    let json = serde_json::to_string(&scenarios).unwrap();
+    let restored = Portfolio::from_json(&json).unwrap();
+    assert_eq!(
+        restored.loans().map(|(name, _)| name).collect::<Vec<_>>(),
+        vec!["first", "second"]
+    );
+    let restored = restored.compute(date(2021, 12, 31)).unwrap();
+    assert_eq!(restored.total_outstanding, result.total_outstanding);
+}
@@ -0,0 +1,143 @@
+use interest_calculator::{
+    InteractiveCalculator, LoanExtraInstallment, LoanInitialization, LoanTransfer, NotableEvents,
+    PrepaymentFee,
+};
+
+use chrono::{Month, NaiveDate};
+
+fn fixed_rate_loan() -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 100000.0,
+        nominal_interest: 5.0,
+        terms: 60,
+        first_installment_month: Month::February,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(NaiveDate::from_ymd(2021, 1, 1), initial)
+}
+
+fn with_extra(prepayment_fee: Option<PrepaymentFee>) -> InteractiveCalculator {
+    let mut calculator = fixed_rate_loan();
+    calculator
+        .add_event_extra_single(
+            NaiveDate::from_ymd(2022, 1, 15),
+            LoanExtraInstallment {
+                amount: 10000.0,
+                policy: None,
+                prepayment_fee,
+            },
+        )
+        .unwrap();
+    calculator
+}
+
+#[test]
+fn flat_and_percentage_fees() {
+    let without = with_extra(None).compute().unwrap();
+    assert_eq!(without.total_prepayment_fee, 0.0);
+
+    let flat = with_extra(Some(PrepaymentFee::Flat(500.0)))
+        .compute()
+        .unwrap();
+    assert_eq!(flat.total_prepayment_fee, 500.0);
+    assert!((flat.total_fee - without.total_fee - 500.0).abs() < 1e-6);
+    assert!((flat.total_cost - without.total_cost - 500.0).abs() < 1e-6);
+    assert!(flat.effective_annual_rate.unwrap() > without.effective_annual_rate.unwrap());
+
+    let percentage = with_extra(Some(PrepaymentFee::Percentage(2.0)))
+        .compute()
+        .unwrap();
+    assert!((percentage.total_prepayment_fee - 200.0).abs() < 1e-6);
+}
+
+#[test]
+fn rate_differential_fee() {
+    // Two years of a 2 % rate differential on the prepaid amount.
+    let fee = PrepaymentFee::RateDifferential {
+        market_interest: 3.0,
+        fixed_until: NaiveDate::from_ymd(2024, 1, 15),
+    };
+    let summary = with_extra(Some(fee)).compute().unwrap();
+    assert!(
+        (summary.total_prepayment_fee - 10000.0 * 0.02 * 730.0 / 365.0).abs() < 1e-6,
+        "{}",
+        summary.total_prepayment_fee
+    );
+
+    // No fee when the market interest is above the contract interest.
+    let fee = PrepaymentFee::RateDifferential {
+        market_interest: 6.0,
+        fixed_until: NaiveDate::from_ymd(2024, 1, 15),
+    };
+    assert_eq!(
+        with_extra(Some(fee))
+            .compute()
+            .unwrap()
+            .total_prepayment_fee,
+        0.0
+    );
+
+    // No fee after the fixed-rate period.
+    let fee = PrepaymentFee::RateDifferential {
+        market_interest: 3.0,
+        fixed_until: NaiveDate::from_ymd(2021, 12, 31),
+    };
+    assert_eq!(
+        with_extra(Some(fee))
+            .compute()
+            .unwrap()
+            .total_prepayment_fee,
+        0.0
+    );
+}
+
+#[test]
+fn transfer_fee_on_outstanding_loan() {
+    let mut calculator = fixed_rate_loan();
+    calculator
+        .add_event_bank_transfer(
+            NaiveDate::from_ymd(2022, 1, 15),
+            LoanTransfer {
+                administration_fee: 0.0,
+                prepayment_fee: Some(PrepaymentFee::Percentage(1.0)),
+            },
+        )
+        .unwrap();
+    let summary = calculator.compute().unwrap();
+
+    let ledger = calculator.ledger().unwrap();
+    let transfer = ledger
+        .iter()
+        .find(|d| d.date == NaiveDate::from_ymd(2022, 1, 15))
+        .unwrap();
+    assert!(transfer
+        .notable_events
+        .iter()
+        .any(|e| matches!(e, NotableEvents::PrepaymentFee(_))));
+    assert!((transfer.prepayment_fee - transfer.outstanding_loan * 0.01).abs() < 1e-6);
+
+    // The fee is payed to the previous bank, and is not part of the transferred loan.
+    assert_eq!(summary.total_prepayment_fee, transfer.prepayment_fee);
+    assert!((summary.total_repayment_installment - 100000.0).abs() < 1e-6);
+}
+
+#[test]
+fn prepayment_fee_from_str() {
+    assert_eq!("250".parse(), Ok(PrepaymentFee::Flat(250.0)));
+    assert_eq!("1.5%".parse(), Ok(PrepaymentFee::Percentage(1.5)));
+    assert_eq!(
+        "3.2% until 2025-06-30".parse(),
+        Ok(PrepaymentFee::RateDifferential {
+            market_interest: 3.2,
+            fixed_until: NaiveDate::from_ymd(2025, 6, 30),
+        })
+    );
+    assert!("a lot".parse::<PrepaymentFee>().is_err());
+
+    let fee = PrepaymentFee::RateDifferential {
+        market_interest: 3.2,
+        fixed_until: NaiveDate::from_ymd(2025, 6, 30),
+    };
+    assert_eq!(fee.to_string().parse(), Ok(fee));
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    FloatingRate, IndexSeries, IndexedRate, InteractiveCalculator, LoanInitialization,
+    MonthlyDueDate, NotableEvents, RateLimit, RateLimits, RecurringInterval,
+};
+
+use chrono::{Month, NaiveDate};
+
+fn limited_loan(floating_rate: FloatingRate, rate_limits: RateLimits) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 1000000.0,
+        nominal_interest: 2.0,
+        terms: 120,
+        due_within_month: MonthlyDueDate::Date(20),
+        first_installment_month: Month::February,
+        floating_rate: Some(floating_rate),
+        rate_limits,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(date(2021, 1, 10), initial)
+}
+
+/// The nominal interest applied on every interest change, by date.
+fn interest_changes(calculator: &InteractiveCalculator) -> Vec<(NaiveDate, f64)> {
+    calculator
+        .ledger()
+        .unwrap()
+        .iter()
+        .flat_map(|d| {
+            d.notable_events.iter().filter_map(move |e| match e {
+                NotableEvents::InterestChange(rate) => Some((d.date, *rate)),
+                _ => None,
+            })
+        })
+        .collect()
+}
+
+#[test]
+fn rate_limits_apply() {
+    let collar = RateLimits {
+        cap: Some(5.0),
+        floor: Some(2.0),
+        max_change: Some(1.0),
+    };
+    assert_eq!(collar.apply(None, 7.0), (5.0, Some(RateLimit::Cap)));
+    assert_eq!(collar.apply(None, 1.0), (2.0, Some(RateLimit::Floor)));
+    assert_eq!(collar.apply(Some(3.0), 3.5), (3.5, None));
+    assert_eq!(collar.apply(Some(3.0), 4.5), (4.0, Some(RateLimit::Change)));
+    assert_eq!(collar.apply(Some(2.5), 1.0), (2.0, Some(RateLimit::Floor)));
+    assert_eq!(collar.apply(Some(4.5), 7.0), (5.0, Some(RateLimit::Cap)));
+
+    assert!(RateLimits::default().is_empty());
+    assert!(collar.validate().is_ok());
+    let inverted = RateLimits {
+        cap: Some(2.0),
+        floor: Some(5.0),
+        max_change: None,
+    };
+    assert!(inverted.validate().is_err());
+}
+
+#[test]
+fn cap_saves_interest() {
+    let path = || FloatingRate::Path(vec![(date(2022, 1, 1), 4.0), (date(2023, 1, 1), 6.0)]);
+    let cap = RateLimits {
+        cap: Some(4.5),
+        ..RateLimits::default()
+    };
+    let capped = limited_loan(path(), cap);
+    let summary = capped.compute().unwrap();
+
+    assert_eq!(summary.rate_cap_triggers, 1);
+    assert_eq!(summary.rate_floor_triggers, 0);
+    assert_eq!(
+        interest_changes(&capped),
+        vec![(date(2022, 1, 1), 4.0), (date(2023, 1, 1), 4.5)]
+    );
+
+    // The interest saved is the difference to the same loan without the cap.
+    let uncapped = limited_loan(path(), RateLimits::default());
+    assert_eq!(uncapped.interest_saved_by_rate_limits().unwrap(), 0.0);
+    let saved = capped.interest_saved_by_rate_limits().unwrap();
+    let uncapped = uncapped.compute().unwrap();
+    assert!(saved > 0.0);
+    assert!((saved - (uncapped.total_interest - summary.total_interest)).abs() < 1e-6);
+}
+
+#[test]
+fn floor_costs_interest() {
+    let path = FloatingRate::Path(vec![(date(2022, 1, 1), 1.0)]);
+    let collar = RateLimits {
+        cap: Some(6.0),
+        floor: Some(1.5),
+        max_change: None,
+    };
+    let floored = limited_loan(path, collar);
+    let summary = floored.compute().unwrap();
+
+    assert_eq!(summary.rate_floor_triggers, 1);
+    assert_eq!(summary.rate_cap_triggers, 0);
+    assert_eq!(interest_changes(&floored), vec![(date(2022, 1, 1), 1.5)]);
+    assert!(floored.interest_saved_by_rate_limits().unwrap() < 0.0);
+}
+
+#[test]
+fn change_limit_catches_up_with_index() {
+    // The index rises by two percentage points at once.
+    let index = IndexSeries::new(vec![(date(2020, 12, 1), 1.0), (date(2021, 3, 1), 3.0)]);
+    let indexed = FloatingRate::Indexed(IndexedRate {
+        index,
+        margin: 1.0,
+        reset: RecurringInterval::Monthly,
+        lookback_days: 0,
+    });
+    let limits = RateLimits {
+        max_change: Some(0.5),
+        ..RateLimits::default()
+    };
+    let calculator = limited_loan(indexed, limits);
+    let summary = calculator.compute().unwrap();
+
+    // The nominal interest moves half a percentage point on every reset,
+    // until it has caught up with the index.
+    let changes: Vec<_> = interest_changes(&calculator)
+        .into_iter()
+        .filter(|(d, _)| *d >= date(2021, 3, 1))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (date(2021, 3, 10), 2.5),
+            (date(2021, 4, 10), 3.0),
+            (date(2021, 5, 10), 3.5),
+            (date(2021, 6, 10), 4.0),
+            (date(2021, 7, 10), 4.0),
+        ]
+    );
+    assert_eq!(summary.rate_change_limit_triggers, 3);
+    assert!(calculator.interest_saved_by_rate_limits().unwrap() > 0.0);
+
+    // Negative limits are rejected.
+    let path = FloatingRate::Path(vec![(date(2022, 1, 1), 4.0)]);
+    let negative = RateLimits {
+        max_change: Some(-0.5),
+        ..RateLimits::default()
+    };
+    assert!(limited_loan(path, negative).compute().is_err());
+}
@@ -0,0 +1,281 @@
+use interest_calculator::{
+    draw_balance, draw_yearly_breakdown, effective_annual_rate, InteractiveCalculator,
+    LoanExtraInstallment, LoanInitialization, LoanRefinance, MonthlyCashFlow, ScheduleEntry,
+    YearlyResult,
+};
+
+use chrono::{Datelike, Month, NaiveDate};
+use plotters::prelude::*;
+
+fn two_year_calculator() -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 10000.0,
+        nominal_interest: 3.0,
+        installment_fee: 50.0,
+        terms: 24,
+        first_installment_month: Month::July,
+        ..LoanInitialization::default()
+    };
+
+    let loan_start_date = NaiveDate::from_ymd(2021, 6, 10);
+    InteractiveCalculator::new(loan_start_date, initial)
+}
+
+#[test]
+fn yearly_breakdown_partial_years() {
+    let calculator = two_year_calculator();
+    let ledger = calculator.ledger().unwrap();
+    let yearly = YearlyResult::from_ledger(&ledger);
+
+    // The loan spans parts of 2021 and 2023.
+    assert_eq!(yearly.len(), 3);
+    assert_eq!(yearly[0].year, 2021);
+    assert_eq!(yearly[0].first_date, NaiveDate::from_ymd(2021, 6, 10));
+    assert_eq!(yearly[0].disbursed, 10000.0);
+    assert_eq!(yearly[1].disbursed, 0.0);
+    assert_eq!(yearly[2].year, 2023);
+    assert_eq!(yearly[2].last_date, NaiveDate::from_ymd(2023, 6, 1));
+
+    // Six installments in 2021, twelve in 2022 and six in 2023.
+    assert!((yearly[0].fee - 6.0 * 50.0).abs() < 1e-9);
+    assert!((yearly[1].fee - 12.0 * 50.0).abs() < 1e-9);
+    assert!((yearly[2].fee - 6.0 * 50.0).abs() < 1e-9);
+    assert!(yearly[2].closing_outstanding_loan.abs() < 1e-6);
+}
+
+#[test]
+fn yearly_breakdown_sums_to_total() {
+    let calculator = two_year_calculator();
+    let total = calculator.compute().unwrap();
+    let yearly = YearlyResult::from_ledger(&calculator.ledger().unwrap());
+
+    let payed: f64 = yearly.iter().map(|y| y.total_payed()).sum();
+    let principal: f64 = yearly.iter().map(|y| y.principal).sum();
+    let interest: f64 = yearly.iter().map(|y| y.interest).sum();
+
+    assert!((payed - total.total_cost).abs() < 1e-6);
+    assert!((principal - total.total_loan).abs() < 1e-6);
+    assert!((interest - total.total_interest).abs() < 1e-6);
+}
+
+#[test]
+fn yearly_breakdown_refinancing_year() {
+    let mut calculator = two_year_calculator();
+    calculator
+        .add_event_refinance(
+            NaiveDate::from_ymd(2022, 3, 15),
+            LoanRefinance {
+                loan_increase: 5000.0,
+                administration_fee: 400.0,
+            },
+        )
+        .unwrap();
+    let total = calculator.compute().unwrap();
+    let yearly = YearlyResult::from_ledger(&calculator.ledger().unwrap());
+
+    // The increase is disbursed within the year of the refinancing.
+    assert_eq!(yearly[0].disbursed, 10000.0);
+    assert_eq!(yearly[1].disbursed, 5000.0);
+    assert!(yearly[2..].iter().all(|y| y.disbursed == 0.0));
+
+    // The administration fee is payed as part of the repayments of the refinanced loan,
+    // rather than as a fee within the year of the refinancing.
+    assert!((yearly[1].fee - 12.0 * 50.0).abs() < 1e-9);
+
+    let payed: f64 = yearly.iter().map(|y| y.total_payed()).sum();
+    let principal: f64 = yearly.iter().map(|y| y.principal).sum();
+    assert!((payed - total.total_cost).abs() < 1e-6);
+    assert!((principal - (15000.0 + 400.0)).abs() < 1e-6);
+    assert!(yearly.last().unwrap().closing_outstanding_loan.abs() < 1e-6);
+}
+
+#[test]
+fn yearly_breakdown_draws_svg() {
+    let calculator = two_year_calculator();
+    let yearly = YearlyResult::from_ledger(&calculator.ledger().unwrap());
+
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, (800, 600)).into_drawing_area();
+        draw_yearly_breakdown(&root, &yearly).unwrap();
+    }
+    assert!(svg.contains("Yearly Payment Breakdown"));
+}
+
+#[test]
+fn yearly_breakdown_draw_empty() {
+    let mut svg = String::new();
+    let root = SVGBackend::with_string(&mut svg, (800, 600)).into_drawing_area();
+    assert!(draw_yearly_breakdown(&root, &[]).is_err());
+}
+
+#[test]
+fn balance_draws_svg_with_overlays() {
+    let calculator = two_year_calculator();
+    let mut other = two_year_calculator();
+    other
+        .add_event_extra_single(
+            NaiveDate::from_ymd(2022, 1, 20),
+            LoanExtraInstallment {
+                amount: 2000.0,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    let overlays = vec![("Scenario B".to_string(), other.ledger().unwrap())];
+
+    let mut svg = String::new();
+    {
+        let root = SVGBackend::with_string(&mut svg, (800, 300)).into_drawing_area();
+        draw_balance(&root, &calculator.ledger().unwrap(), &overlays).unwrap();
+    }
+    assert!(svg.contains("Cumulative cost"));
+    assert!(svg.contains("Scenario B"));
+
+    let mut svg = String::new();
+    let root = SVGBackend::with_string(&mut svg, (800, 300)).into_drawing_area();
+    assert!(draw_balance(&root, &[], &overlays).is_err());
+}
+
+#[test]
+fn monthly_cash_flow_by_calendar_month() {
+    let mut calculator = two_year_calculator();
+    calculator
+        .add_event_extra_single(
+            NaiveDate::from_ymd(2021, 9, 20),
+            LoanExtraInstallment {
+                amount: 1000.0,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    let total = calculator.compute().unwrap();
+    let monthly = MonthlyCashFlow::from_ledger(&calculator.ledger().unwrap());
+
+    // The disbursement month is followed by a month for every installment.
+    assert_eq!((monthly[0].year, monthly[0].month), (2021, 6));
+    assert_eq!(monthly[0].disbursed, 10000.0);
+    assert_eq!(monthly[0].installments(), 0.0);
+    assert!((monthly[0].closing_outstanding_loan - 10000.0).abs() < 1e-9);
+    assert_eq!((monthly[1].year, monthly[1].month), (2021, 7));
+    assert!((monthly[1].fee - 50.0).abs() < 1e-9);
+    assert!(monthly[1].interest > 0.0 && monthly[1].principal > 0.0);
+
+    assert_eq!((monthly[3].year, monthly[3].month), (2021, 9));
+    assert_eq!(monthly[3].extra, 1000.0);
+    assert!(monthly[3].closing_outstanding_loan < monthly[2].closing_outstanding_loan - 1000.0);
+
+    let last = monthly.last().unwrap();
+    assert_eq!(
+        (last.year, last.month),
+        (total.end_date.year(), total.end_date.month())
+    );
+    assert!(last.closing_outstanding_loan.abs() < 1e-6);
+    let payed: f64 = monthly.iter().map(|m| m.total_payed()).sum();
+    assert!((payed - total.total_cost).abs() < 1e-6);
+}
+
+#[test]
+fn monthly_cash_flow_fills_quiet_months() {
+    let mut calculator = two_year_calculator();
+    calculator
+        .add_event_extra_single(
+            NaiveDate::from_ymd(2021, 7, 15),
+            LoanExtraInstallment {
+                amount: 500.0,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+
+    // Only the disbursement and a few installments months apart.
+    let ledger: Vec<_> = calculator
+        .ledger()
+        .unwrap()
+        .into_iter()
+        .filter(|d| d.disbursed > 0.0 || [7, 10].contains(&d.date.month()))
+        .filter(|d| d.date.year() == 2021)
+        .collect();
+    let monthly = MonthlyCashFlow::from_ledger(&ledger);
+
+    let months: Vec<u32> = monthly.iter().map(|m| m.month).collect();
+    assert_eq!(months, vec![6, 7, 8, 9, 10]);
+    for quiet in monthly[2..4].iter() {
+        assert_eq!(quiet.total_payed(), 0.0);
+        assert_eq!(
+            quiet.closing_outstanding_loan,
+            monthly[1].closing_outstanding_loan
+        );
+    }
+    assert_eq!(monthly[1].extra, 500.0);
+    assert!(MonthlyCashFlow::from_ledger(&[]).is_empty());
+}
+
+#[test]
+fn schedule_highlights_events() {
+    let mut calculator = two_year_calculator();
+    calculator
+        .add_event_extra_single(
+            NaiveDate::from_ymd(2021, 9, 20),
+            LoanExtraInstallment {
+                amount: 1000.0,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    let schedule = ScheduleEntry::from_ledger(&calculator.ledger().unwrap());
+
+    // The disbursement and the extra installment take effect outside the installments.
+    let events: Vec<NaiveDate> = schedule
+        .iter()
+        .filter(|e| e.is_event())
+        .map(|e| e.date)
+        .collect();
+    assert_eq!(
+        events,
+        vec![
+            NaiveDate::from_ymd(2021, 6, 10),
+            NaiveDate::from_ymd(2021, 9, 20)
+        ]
+    );
+
+    let installments = schedule.iter().filter(|e| e.principal > 0.0).count();
+    let total = calculator.compute().unwrap();
+    assert_eq!(installments as i32, total.completed_terms);
+    for entry in schedule.iter() {
+        let portions = entry.interest + entry.principal + entry.fee + entry.extra;
+        assert!((entry.payment - portions).abs() < 1e-6);
+    }
+}
+
+#[test]
+fn effective_rate_reflects_fees() {
+    let mut initial = LoanInitialization {
+        loan: 10000.0,
+        nominal_interest: 3.0,
+        terms: 24,
+        first_installment_month: Month::July,
+        ..LoanInitialization::default()
+    };
+    let loan_start_date = NaiveDate::from_ymd(2021, 6, 1);
+
+    let ledger = InteractiveCalculator::new(loan_start_date, initial.clone())
+        .ledger()
+        .unwrap();
+    let without_fees = effective_annual_rate(&ledger).unwrap();
+    // Monthly compounding of 3% nominal interest.
+    assert!((without_fees - 3.04).abs() < 0.02, "{}", without_fees);
+
+    initial.installment_fee = 50.0;
+    let ledger = InteractiveCalculator::new(loan_start_date, initial)
+        .ledger()
+        .unwrap();
+    let with_fees = effective_annual_rate(&ledger).unwrap();
+    assert!(with_fees > without_fees + 5.0, "{}", with_fees);
+
+    assert!(effective_annual_rate(&[]).is_err());
+}
@@ -0,0 +1,122 @@
+use interest_calculator::{
+    CompoundingStrategy, InteractiveCalculator, LoanEvent, LoanInitialization, LoanInterestChange,
+    LoanRecurringExtraInstallments, MonthlyDueDate, RecurringInterval, Scenario, ScenarioEvent,
+};
+
+use chrono::{Month, NaiveDate};
+use std::num::NonZeroU32;
+
+fn calculator_with_events() -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 12000.0,
+        nominal_interest: 2.0,
+        installment_fee: 45.0,
+        terms: 12,
+        due_within_month: MonthlyDueDate::Date(20),
+        first_installment_month: Month::February,
+        ..LoanInitialization::default()
+    };
+
+    let mut calculator = InteractiveCalculator::new(NaiveDate::from_ymd(2021, 1, 10), initial);
+    calculator
+        .add_event_interest_change(
+            NaiveDate::from_ymd(2021, 4, 1),
+            LoanInterestChange {
+                nominal_interest: 3.5,
+            },
+        )
+        .unwrap();
+    calculator
+        .add_event_extra_recurring(
+            NaiveDate::from_ymd(2021, 5, 25),
+            LoanRecurringExtraInstallments {
+                amount: 500.0,
+                count: NonZeroU32::new(3).unwrap(),
+                recurring_interval: RecurringInterval::Monthly,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    calculator
+}
+
+#[test]
+fn scenario_round_trip_preserves_results() {
+    let calculator = calculator_with_events();
+    let scenario = Scenario::from_calculator("Keep current loan", &calculator);
+
+    let json = scenario.to_json().unwrap();
+    let restored = Scenario::from_json(&json).unwrap();
+    assert_eq!(restored.name, "Keep current loan");
+    assert_eq!(restored.events.len(), 3);
+
+    let expected = calculator.compute().unwrap();
+    let summary = restored.to_calculator().unwrap().compute().unwrap();
+    assert_eq!(summary.total_cost, expected.total_cost);
+    assert_eq!(summary.total_interest, expected.total_interest);
+    assert_eq!(summary.end_date, expected.end_date);
+}
+
+#[test]
+fn scenario_round_trip_preserves_compounding() {
+    let mut calculator = calculator_with_events();
+    calculator.set_compounding_strategy(CompoundingStrategy::EndOfMonth);
+    let scenario = Scenario::from_calculator("Monthly compounding", &calculator);
+
+    let restored = Scenario::from_json(&scenario.to_json().unwrap())
+        .unwrap()
+        .to_calculator()
+        .unwrap();
+    assert_eq!(
+        restored.compounding_strategy(),
+        CompoundingStrategy::EndOfMonth
+    );
+    assert_eq!(
+        restored.compute().unwrap().total_interest,
+        calculator.compute().unwrap().total_interest
+    );
+
+    // Scenarios stored before the compounding strategy was part of the format.
+    let mut json: serde_json::Value = serde_json::from_str(&scenario.to_json().unwrap()).unwrap();
+    json.as_object_mut().unwrap().remove("compounding_strategy");
+    let restored = Scenario::from_json(&json.to_string()).unwrap();
+    assert_eq!(
+        restored.compounding_strategy,
+        CompoundingStrategy::OnInstallment
+    );
+}
+
+#[test]
+fn scenario_file_round_trip() {
+    let path = std::env::temp_dir().join("interest-calculator-scenario-test.json");
+    let scenario = Scenario::from_calculator("File", &calculator_with_events());
+    scenario.save(&path).unwrap();
+
+    let loaded = Scenario::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.name, "File");
+    assert_eq!(loaded.events.len(), scenario.events.len());
+}
+
+#[test]
+fn scenario_must_start_with_initial_event() {
+    let scenario = Scenario {
+        name: "Broken".to_string(),
+        events: vec![ScenarioEvent {
+            date: NaiveDate::from_ymd(2021, 1, 10),
+            event: LoanEvent::InterestChange(LoanInterestChange {
+                nominal_interest: 3.5,
+            }),
+        }],
+        compounding_strategy: CompoundingStrategy::default(),
+    };
+    assert!(scenario.to_calculator().is_err());
+
+    let mut scenario = Scenario::from_calculator("Early event", &calculator_with_events());
+    scenario.events[1].date = NaiveDate::from_ymd(2020, 12, 1);
+    assert!(scenario.to_calculator().is_err());
+
+    assert!(Scenario::from_json("{\"name\": \"Missing events\"}").is_err());
+}
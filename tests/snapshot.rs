@@ -0,0 +1,114 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    BusinessDayConvention, InteractiveCalculator, LoanInitialization, LoanInterestChange,
+    LoanRepaymentFreeze,
+};
+
+use chrono::Month;
+use std::num::NonZeroU32;
+
+fn loan() -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 300000.0,
+        nominal_interest: 3.0,
+        installment_fee: 45.0,
+        terms: 36,
+        first_installment_month: Month::February,
+        business_day_convention: BusinessDayConvention::Following,
+        ..LoanInitialization::default()
+    };
+
+    let mut calculator = InteractiveCalculator::new(date(2021, 1, 15), initial);
+    calculator
+        .add_event_interest_change(
+            date(2021, 6, 10),
+            LoanInterestChange {
+                nominal_interest: 4.0,
+            },
+        )
+        .unwrap();
+    calculator
+        .add_event_repayment_freeze(
+            date(2021, 9, 15),
+            LoanRepaymentFreeze {
+                count: NonZeroU32::new(2).unwrap(),
+            },
+        )
+        .unwrap();
+    calculator
+}
+
+#[test]
+fn snapshot_follows_ledger() {
+    let calculator = loan();
+    let ledger = calculator.ledger().unwrap();
+
+    for query in [date(2021, 1, 15), date(2021, 5, 20), date(2021, 10, 1)].iter() {
+        let snapshot = calculator.snapshot(*query).unwrap();
+        let last = ledger.iter().rev().find(|d| d.date <= *query).unwrap();
+        let completed = ledger
+            .iter()
+            .filter(|d| d.date <= *query && d.repayment_installment > 0.0)
+            .count();
+
+        assert_eq!(snapshot.date, *query);
+        assert_eq!(snapshot.outstanding_loan, last.outstanding_loan);
+        assert_eq!(snapshot.completed_terms as usize, completed);
+        assert_eq!(snapshot.planned_terms, 36);
+        assert!(!snapshot.finished);
+    }
+
+    let before_change = calculator.snapshot(date(2021, 6, 9)).unwrap();
+    let after_change = calculator.snapshot(date(2021, 6, 10)).unwrap();
+    assert_eq!(before_change.nominal_interest, 3.0);
+    assert_eq!(after_change.nominal_interest, 4.0);
+    assert!(after_change.term_payment > before_change.term_payment);
+    assert_eq!(after_change.installment_fee, 45.0);
+
+    // The frozen installments are pending until their due dates pass.
+    let frozen = calculator.snapshot(date(2021, 9, 20)).unwrap();
+    assert_eq!(frozen.pending_interest_only_terms, 2);
+    assert_eq!(frozen.next_installment_date, Some(date(2021, 10, 1)));
+    let frozen = calculator.snapshot(date(2021, 10, 5)).unwrap();
+    assert_eq!(frozen.pending_interest_only_terms, 1);
+    assert_eq!(frozen.next_installment_date, Some(date(2021, 11, 1)));
+    assert!(frozen.interest_since_last_installment > 0.0);
+
+    // After the last installment, the loan is repayed in full.
+    let end = ledger.last().unwrap().date;
+    let finished = calculator
+        .snapshot(end + chrono::Duration::days(100))
+        .unwrap();
+    assert!(finished.finished);
+    assert_eq!(finished.next_installment_date, None);
+    assert_eq!(finished.completed_terms, 36);
+    assert!(calculator.snapshot(end).unwrap().finished);
+    assert!(!calculator.snapshot(end.pred()).unwrap().finished);
+}
+
+#[test]
+fn batch_snapshots() {
+    let calculator = loan();
+    let dates = [
+        date(2023, 1, 1),
+        date(2021, 3, 1),
+        date(2021, 3, 1),
+        date(2022, 7, 31),
+        date(2021, 1, 15),
+    ];
+
+    // The snapshots are in the order of the dates, and equal to single snapshots.
+    let snapshots = calculator.snapshots(&dates).unwrap();
+    assert_eq!(snapshots.len(), dates.len());
+    for (query, snapshot) in dates.iter().zip(snapshots.iter()) {
+        assert_eq!(&calculator.snapshot(*query).unwrap(), snapshot);
+    }
+    assert!(calculator.snapshots(&[]).unwrap().is_empty());
+
+    assert!(calculator.snapshot(date(2021, 1, 14)).is_err());
+    assert!(calculator
+        .snapshots(&[date(2022, 1, 1), date(2020, 1, 1)])
+        .is_err());
+}
@@ -0,0 +1,124 @@
+mod common;
+
+use common::date;
+use interest_calculator::{
+    InteractiveCalculator, LoanExtraInstallment, LoanInitialization, LoanTransfer, MonthlyDueDate,
+    NotableEvents, PrepaymentFee, TaxStatement, YearlyResult,
+};
+
+use chrono::Month;
+
+fn mortgage() -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 1500000.0,
+        nominal_interest: 4.0,
+        installment_fee: 50.0,
+        terms: 36,
+        due_within_month: MonthlyDueDate::Date(20),
+        first_installment_month: Month::July,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(date(2021, 6, 10), initial)
+}
+
+#[test]
+fn statement_of_partial_years() {
+    let calculator = mortgage();
+    let ledger = calculator.ledger().unwrap();
+    let statements = TaxStatement::from_ledger(&ledger);
+
+    // The loan starts in June 2021 and ends in June 2024.
+    let years: Vec<i32> = statements.iter().map(|s| s.year).collect();
+    assert_eq!(years, vec![2021, 2022, 2023, 2024]);
+    assert!(statements.iter().all(|s| s.lenders.len() == 1));
+    assert_eq!(statements[0].lenders[0].first_date, date(2021, 6, 10));
+    assert_eq!(statements[3].lenders[0].last_date, date(2024, 6, 20));
+
+    // The balance on 31 Dec, while the loan is repaid within the last year.
+    let balance = calculator.snapshot(date(2021, 12, 31)).unwrap();
+    assert_eq!(statements[0].closing_balance(), balance.outstanding_loan);
+    assert!(statements[3].closing_balance().abs() < 1e-6);
+
+    // Six installments in 2021, and twelve in 2022.
+    assert!((statements[0].fee() - 6.0 * 50.0).abs() < 1e-9);
+    assert!((statements[1].fee() - 12.0 * 50.0).abs() < 1e-9);
+
+    let total = calculator.compute().unwrap();
+    let interest: f64 = statements.iter().map(|s| s.interest()).sum();
+    let principal: f64 = statements.iter().map(|s| s.principal()).sum();
+    assert!((interest - total.total_interest).abs() < 1e-6);
+    assert!((principal - 1500000.0).abs() < 1e-6);
+}
+
+#[test]
+fn statement_of_several_lenders() {
+    let mut calculator = mortgage();
+    let transfer_date = date(2022, 8, 5);
+    calculator
+        .add_event_bank_transfer(
+            transfer_date,
+            LoanTransfer {
+                administration_fee: 1000.0,
+                prepayment_fee: Some(PrepaymentFee::Flat(750.0)),
+            },
+        )
+        .unwrap();
+    calculator
+        .add_event_extra_single(
+            date(2022, 11, 1),
+            LoanExtraInstallment {
+                amount: 100000.0,
+                policy: None,
+                prepayment_fee: None,
+            },
+        )
+        .unwrap();
+    let ledger = calculator.ledger().unwrap();
+    let statements = TaxStatement::from_ledger(&ledger);
+
+    // The year of the transfer is divided between the previous and the new lender.
+    let transfer_year = &statements[1];
+    assert_eq!(statements[0].lenders.len(), 1);
+    assert_eq!(transfer_year.lenders.len(), 2);
+    assert!(statements[2..].iter().all(|s| s.lenders.len() == 1));
+    assert!(statements[2..].iter().all(|s| s.lenders[0].lender == 1));
+
+    let (previous, new) = (&transfer_year.lenders[0], &transfer_year.lenders[1]);
+    assert_eq!((previous.lender, new.lender), (0, 1));
+    assert_eq!(previous.first_date, date(2022, 1, 20));
+    assert_eq!(previous.last_date, transfer_date);
+    assert!(new.first_date > transfer_date);
+    assert_eq!(new.last_date, date(2022, 12, 31));
+
+    // The previous lender is payed the interest accrued until the transfer, along with
+    // the prepayment fee, and the loan is transferred with the fee of the new lender.
+    let transferred = ledger
+        .iter()
+        .find(|d| d.date == transfer_date)
+        .unwrap()
+        .notable_events
+        .iter()
+        .find_map(|e| match e {
+            NotableEvents::BankTransfer(outstanding) => Some(*outstanding),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(previous.closing_balance, transferred);
+    assert!((previous.fee - (7.0 * 50.0 + 750.0)).abs() < 1e-9);
+    assert!((new.fee - 5.0 * 50.0).abs() < 1e-9);
+    assert!(new.principal > 100000.0);
+
+    // The statements of every lender sum to the yearly payments.
+    for (statement, year) in statements
+        .iter()
+        .zip(YearlyResult::from_ledger(&ledger).iter())
+    {
+        assert_eq!(statement.year, year.year);
+        assert!((statement.interest() - year.interest).abs() < 1e-6);
+        assert!((statement.fee() - year.fee).abs() < 1e-6);
+        assert!((statement.principal() - (year.principal + year.extra)).abs() < 1e-6);
+        assert_eq!(statement.closing_balance(), year.closing_outstanding_loan);
+    }
+    assert!(TaxStatement::from_ledger(&[]).is_empty());
+}
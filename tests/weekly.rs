@@ -0,0 +1,106 @@
+mod common;
+
+use common::date;
+use interest_calculator::{InteractiveCalculator, LoanInitialization, TermsPerYear};
+
+use chrono::{Datelike, Month, NaiveDate, Weekday};
+
+fn loan(
+    disbursement: NaiveDate,
+    terms_per_year: TermsPerYear,
+    due_weekday: Option<Weekday>,
+) -> InteractiveCalculator {
+    let initial = LoanInitialization {
+        loan: 300000.0,
+        nominal_interest: 5.0,
+        terms: 2 * terms_per_year.to_u32(),
+        terms_per_year,
+        first_installment_month: Month::February,
+        due_weekday,
+        ..LoanInitialization::default()
+    };
+
+    InteractiveCalculator::new(disbursement, initial)
+}
+
+/// The date and payment of every scheduled repayment installment.
+fn payments(calculator: &InteractiveCalculator) -> Vec<(NaiveDate, f64)> {
+    calculator
+        .ledger()
+        .unwrap()
+        .iter()
+        .filter(|d| d.repayment_installment > 0.0)
+        .map(|d| (d.date, d.repayed))
+        .collect()
+}
+
+#[test]
+fn biweekly_terms_on_fixed_weekday() {
+    // The first Friday on or after the 1st of February, two weeks after the disbursement.
+    let calculator = loan(
+        date(2021, 1, 22),
+        TermsPerYear::TwentySix,
+        Some(Weekday::Fri),
+    );
+    let summary = calculator.compute().unwrap();
+
+    let payments = payments(&calculator);
+    assert_eq!(payments.len(), 52);
+    assert_eq!(payments[0].0, date(2021, 2, 5));
+    for (previous, next) in payments.iter().zip(payments.iter().skip(1)) {
+        assert_eq!((next.0 - previous.0).num_days(), 14);
+        assert_eq!(next.0.weekday(), Weekday::Fri);
+    }
+
+    // Every term accrues the interest of exactly 14 days, such that the annuity
+    // repays the loan with equal payments, including the last.
+    assert!(payments
+        .iter()
+        .all(|(_, payment)| (payment - summary.term_payment).abs() < 1e-6));
+
+    // The effective rate reflects compounding every 14 days.
+    let expected = ((1.0 + 0.05 * 14.0 / 365.0_f64).powf(365.0 / 14.0) - 1.0) * 100.0;
+    assert!(
+        (summary.effective_annual_rate.unwrap() - expected).abs() < 1e-3,
+        "{:?}",
+        summary.effective_annual_rate
+    );
+}
+
+#[test]
+fn weekly_terms_keep_weekday_of_first_due_date() {
+    // The 1st of February 2021 is a Monday.
+    let calculator = loan(date(2021, 1, 10), TermsPerYear::FiftyTwo, None);
+    let summary = calculator.compute().unwrap();
+
+    let payments = payments(&calculator);
+    assert_eq!(summary.completed_terms, 104);
+    assert_eq!(payments.len(), 104);
+    assert_eq!(payments[0].0, date(2021, 2, 1));
+    assert!(payments.iter().all(|(d, _)| d.weekday() == Weekday::Mon));
+    assert_eq!(
+        summary.end_date,
+        date(2021, 2, 1) + chrono::Duration::weeks(103)
+    );
+}
+
+#[test]
+fn more_frequent_terms_compound_more_often() {
+    let monthly = loan(date(2021, 1, 1), TermsPerYear::Twelve, None);
+    let biweekly = loan(
+        date(2021, 1, 22),
+        TermsPerYear::TwentySix,
+        Some(Weekday::Fri),
+    );
+    let weekly = loan(
+        date(2021, 1, 29),
+        TermsPerYear::FiftyTwo,
+        Some(Weekday::Fri),
+    );
+
+    let monthly = monthly.compute().unwrap().effective_annual_rate.unwrap();
+    let biweekly = biweekly.compute().unwrap().effective_annual_rate.unwrap();
+    let weekly = weekly.compute().unwrap().effective_annual_rate.unwrap();
+    assert!(monthly < biweekly);
+    assert!(biweekly < weekly);
+}